@@ -57,3 +57,12 @@ where
 {
     bracket(w, &"[", &"]", middle)
 }
+
+/// Name of a variable with given index: `a` to `z`, followed by `x27`, `x28`, ...
+pub fn variable_name(idx: usize) -> String {
+    if idx < 26 {
+        ((b'a' + idx as u8) as char).to_string()
+    } else {
+        format!("x{}", idx + 1)
+    }
+}
@@ -29,9 +29,15 @@ impl Color {
     #[allow(clippy::unreadable_literal)]
     pub const RED: Color = Color::from_hex(0xf92672ff);
 
+    #[allow(clippy::unreadable_literal)]
+    pub const GREEN: Color = Color::from_hex(0x2fb344ff);
+
     #[allow(clippy::unreadable_literal)]
     pub const BLACK: Color = Color::from_hex(0x000000ff);
 
+    #[allow(clippy::unreadable_literal)]
+    pub const WHITE: Color = Color::from_hex(0xffffffff);
+
     #[allow(clippy::unreadable_literal)]
     pub const LIGHT_GRAY: Color = Color::from_hex(0xccccccff);
 
@@ -265,11 +271,17 @@ pub trait Canvas {
     }
 
     fn tile_position(x: u8, y: u8) -> V2f {
+        Self::wide_tile_position(u64::from(x), u64::from(y))
+    }
+
+    /// Same as [`Canvas::tile_position`], but for positions with more than 255 tiles in a row
+    fn wide_tile_position(x: u64, y: u64) -> V2f {
         let tile_size = Self::tile_size();
         let grid_weight = Self::thick_line_weight();
+        let (x, y) = (x as f32, y as f32);
         V2f {
-            x: (x as f32).mul_add(tile_size.x, (x + 1) as f32 * grid_weight),
-            y: (y as f32).mul_add(tile_size.y, (y + 1) as f32 * grid_weight),
+            x: x.mul_add(tile_size.x, (x + 1.0) * grid_weight),
+            y: y.mul_add(tile_size.y, (y + 1.0) * grid_weight),
         }
     }
 }
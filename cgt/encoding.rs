@@ -0,0 +1,195 @@
+//! Compact binary encoding of game positions and values, used to store them on disk
+
+use crate::numeric::{dyadic_rational_number::DyadicRationalNumber, nimber::Nimber};
+
+/// Type that can be stored in a compact binary format
+pub trait Encode: Sized {
+    /// Append binary representation to the buffer
+    fn encode(&self, buf: &mut Vec<u8>);
+
+    /// Read value from the beginning of the input and advance it past the value.
+    /// Returns `None` if the input is malformed or truncated.
+    fn decode(input: &mut &[u8]) -> Option<Self>;
+}
+
+impl Encode for u8 {
+    #[inline]
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(*self);
+    }
+
+    #[inline]
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        let (&byte, rest) = input.split_first()?;
+        *input = rest;
+        Some(byte)
+    }
+}
+
+impl Encode for bool {
+    #[inline]
+    fn encode(&self, buf: &mut Vec<u8>) {
+        u8::from(*self).encode(buf);
+    }
+
+    #[inline]
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        match u8::decode(input)? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+/// Unsigned integers are stored as LEB128 varints
+impl Encode for u64 {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut value = *self;
+        while value >= 0x80 {
+            buf.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        buf.push(value as u8);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = u8::decode(input)?;
+            value |= u64::from(byte & 0x7f).checked_shl(shift)?;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
+
+/// Signed integers are zigzag encoded, so values close to zero stay short
+impl Encode for i64 {
+    #[inline]
+    fn encode(&self, buf: &mut Vec<u8>) {
+        (((*self << 1) ^ (*self >> 63)) as u64).encode(buf);
+    }
+
+    #[inline]
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        let value = u64::decode(input)?;
+        Some((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+}
+
+macro_rules! impl_encode_via {
+    ($ty:ty, $via:ty) => {
+        impl Encode for $ty {
+            #[inline]
+            fn encode(&self, buf: &mut Vec<u8>) {
+                <$via>::from(*self).encode(buf);
+            }
+
+            #[inline]
+            fn decode(input: &mut &[u8]) -> Option<Self> {
+                <$via>::decode(input).and_then(|value| value.try_into().ok())
+            }
+        }
+    };
+}
+
+impl_encode_via!(u32, u64);
+impl_encode_via!(i32, i64);
+impl_encode_via!(char, u32);
+
+impl Encode for usize {
+    #[inline]
+    fn encode(&self, buf: &mut Vec<u8>) {
+        (*self as u64).encode(buf);
+    }
+
+    #[inline]
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        u64::decode(input).and_then(|value| value.try_into().ok())
+    }
+}
+
+impl<T> Encode for Vec<T>
+where
+    T: Encode,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.len().encode(buf);
+        for item in self {
+            item.encode(buf);
+        }
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        let len = usize::decode(input)?;
+        // Every item takes at least one byte, so do not trust the length blindly
+        let mut items = Vec::with_capacity(len.min(input.len()));
+        for _ in 0..len {
+            items.push(T::decode(input)?);
+        }
+        Some(items)
+    }
+}
+
+impl Encode for Nimber {
+    #[inline]
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.value().encode(buf);
+    }
+
+    #[inline]
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        u32::decode(input).map(Nimber::new)
+    }
+}
+
+impl Encode for DyadicRationalNumber {
+    #[inline]
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.numerator().encode(buf);
+        self.denominator_exponent().encode(buf);
+    }
+
+    #[inline]
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        let numerator = i64::decode(input)?;
+        let denominator_exponent = u32::decode(input)?;
+        Some(DyadicRationalNumber::new(numerator, denominator_exponent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip<T: Encode + PartialEq + std::fmt::Debug>(value: T) {
+        let mut buf = Vec::new();
+        value.encode(&mut buf);
+        let mut input = buf.as_slice();
+        assert_eq!(T::decode(&mut input), Some(value));
+        assert!(input.is_empty());
+        assert_eq!(T::decode(&mut &buf[..buf.len() - 1]), None);
+    }
+
+    #[test]
+    fn encoding_roundtrips() {
+        for value in [0, 1, 127, 128, 300, u64::MAX] {
+            roundtrip(value);
+        }
+        for value in [0, -1, 1, -64, 64, i64::MIN, i64::MAX] {
+            roundtrip(value);
+        }
+        roundtrip(true);
+        roundtrip('#');
+        roundtrip(vec![1u32, 2, 300]);
+        roundtrip(DyadicRationalNumber::new(-3, 4));
+        roundtrip(Nimber::new(42));
+
+        let mut buf = Vec::new();
+        u64::MAX.encode(&mut buf);
+        assert_eq!(u32::decode(&mut buf.as_slice()), None);
+    }
+}
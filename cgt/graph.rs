@@ -9,11 +9,13 @@ use crate::{
 };
 
 pub mod adjacency_matrix;
+pub mod canonical;
 pub mod layout;
 pub mod resolving_set;
 
 /// Graph vertex. We assume that all graphs that we implement use 0-based indexing for their vertices
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexIndex {
     /// 0-based index of a vertex in the graph
     pub index: usize,
@@ -132,6 +134,64 @@ pub trait Graph<V>: Sized {
         seen.into_iter().all(|b| b)
     }
 
+    /// Get vertices of connected components of the graph, ignoring direction of edges
+    fn connected_components(&self) -> Vec<Vec<VertexIndex>> {
+        let mut seen = vec![false; self.size()];
+        let mut components = Vec::new();
+
+        for initial in self.vertex_indices() {
+            if seen[initial.index] {
+                continue;
+            }
+
+            let mut component = Vec::new();
+            let mut queue: VecDeque<VertexIndex> = VecDeque::new();
+            seen[initial.index] = true;
+            queue.push_back(initial);
+
+            while let Some(v) = queue.pop_front() {
+                component.push(v);
+                for u in self.vertex_indices() {
+                    if (self.are_adjacent(v, u) || self.are_adjacent(u, v)) && !seen[u.index] {
+                        seen[u.index] = true;
+                        queue.push_back(u);
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        components
+    }
+
+    /// Create a subgraph induced by given vertices. Vertex `i` of the new graph is `vertices[i]`
+    /// of the original graph.
+    #[must_use]
+    fn induced_subgraph(&self, vertices: &[VertexIndex]) -> Self
+    where
+        V: Clone,
+    {
+        let mut subgraph = Self::empty(
+            &vertices
+                .iter()
+                .map(|&v| self.get_vertex(v).clone())
+                .collect::<Vec<_>>(),
+        );
+        for (new_v, &old_v) in vertices.iter().enumerate() {
+            for (new_u, &old_u) in vertices.iter().enumerate() {
+                if self.are_adjacent(old_v, old_u) {
+                    subgraph.connect(
+                        VertexIndex { index: new_v },
+                        VertexIndex { index: new_u },
+                        true,
+                    );
+                }
+            }
+        }
+        subgraph
+    }
+
     /// Create a graph from list of edges
     fn from_edges(edges: &[(VertexIndex, VertexIndex)], vertices: &[V]) -> Self;
 
@@ -0,0 +1,230 @@
+//! Canonical labeling of graphs, i.e. ordering vertices such that isomorphic graphs become equal
+//! after relabeling.
+//!
+//! Uses individualization-refinement search, where interchangeable twin vertices are tried only
+//! once. This is exponential for some highly symmetric graphs, but fast on graphs arising as game
+//! positions.
+
+use crate::graph::{Graph, VertexIndex};
+
+/// Get canonical order of vertices, isomorphisms must preserve vertex values
+///
+/// See [`canonical_order_by_key`]
+pub fn canonical_order<V, G>(graph: &G) -> Vec<VertexIndex>
+where
+    V: Ord,
+    G: Graph<V>,
+{
+    canonical_order_by_key(graph, |v| graph.get_vertex(v))
+}
+
+/// Get canonical order of vertices, isomorphisms must preserve keys of vertices. Graphs that are
+/// isomorphic have the same [`Graph::induced_subgraph`] of vertices in canonical order.
+pub fn canonical_order_by_key<V, G, K>(
+    graph: &G,
+    key: impl Fn(VertexIndex) -> K,
+) -> Vec<VertexIndex>
+where
+    G: Graph<V>,
+    K: Ord,
+{
+    let mut vertices = graph.vertex_indices().collect::<Vec<_>>();
+    vertices.sort_by_key(|&v| key(v));
+
+    let mut cells: Vec<Vec<VertexIndex>> = Vec::new();
+    for v in vertices {
+        match cells.last_mut() {
+            Some(cell) if key(cell[0]) == key(v) => cell.push(v),
+            _ => cells.push(vec![v]),
+        }
+    }
+
+    let mut search = Search { graph, best: None };
+    search.visit(cells);
+    search.best.map_or_else(Vec::new, |(_, order)| order)
+}
+
+/// Relabel graph with its canonical order
+///
+/// See [`canonical_order`]
+pub fn canonical_form<V, G>(graph: &G) -> G
+where
+    V: Ord + Clone,
+    G: Graph<V>,
+{
+    graph.induced_subgraph(&canonical_order(graph))
+}
+
+/// Split graph into connected components, each relabeled with its canonical order
+///
+/// See [`Graph::connected_components`] and [`canonical_order`]
+pub fn canonical_components<V, G>(graph: &G) -> Vec<G>
+where
+    V: Ord + Clone,
+    G: Graph<V>,
+{
+    graph
+        .connected_components()
+        .into_iter()
+        .map(|component| canonical_form(&graph.induced_subgraph(&component)))
+        .collect()
+}
+
+struct Search<'g, G> {
+    graph: &'g G,
+    best: Option<(Vec<bool>, Vec<VertexIndex>)>,
+}
+
+impl<G> Search<'_, G> {
+    fn visit<V>(&mut self, cells: Vec<Vec<VertexIndex>>)
+    where
+        G: Graph<V>,
+    {
+        let cells = self.refine(cells);
+
+        let Some(target) = cells.iter().position(|cell| cell.len() > 1) else {
+            let order = cells.into_iter().flatten().collect::<Vec<_>>();
+            let certificate = order
+                .iter()
+                .flat_map(|&v| order.iter().map(move |&u| (v, u)))
+                .map(|(v, u)| self.graph.are_adjacent(v, u))
+                .collect::<Vec<_>>();
+            if self
+                .best
+                .as_ref()
+                .is_none_or(|(best, _)| certificate < *best)
+            {
+                self.best = Some((certificate, order));
+            }
+            return;
+        };
+
+        let mut individualized: Vec<VertexIndex> = Vec::new();
+        for &v in &cells[target] {
+            // Swapping twins is an automorphism that preserves the partition, so their subtrees
+            // yield the same leaves
+            if individualized.iter().any(|&u| self.are_twins(u, v)) {
+                continue;
+            }
+            individualized.push(v);
+
+            let mut next = cells.clone();
+            next[target].retain(|&u| u != v);
+            next.insert(target, vec![v]);
+            self.visit(next);
+        }
+    }
+
+    /// Split cells by number of neighbors in other cells, until partition is equitable
+    fn refine<V>(&self, mut cells: Vec<Vec<VertexIndex>>) -> Vec<Vec<VertexIndex>>
+    where
+        G: Graph<V>,
+    {
+        loop {
+            let mut cell_of = vec![0; self.graph.size()];
+            for (idx, cell) in cells.iter().enumerate() {
+                for v in cell {
+                    cell_of[v.index] = idx;
+                }
+            }
+
+            let mut refined: Vec<Vec<VertexIndex>> = Vec::with_capacity(cells.len());
+            for cell in &cells {
+                let mut signatures = cell
+                    .iter()
+                    .map(|&v| {
+                        let mut signature = vec![0usize; cells.len()];
+                        for u in self.graph.adjacent_to(v) {
+                            signature[cell_of[u.index]] += 1;
+                        }
+                        (signature, v)
+                    })
+                    .collect::<Vec<_>>();
+                signatures.sort();
+
+                let mut previous: Option<&Vec<usize>> = None;
+                for (signature, v) in &signatures {
+                    match refined.last_mut() {
+                        Some(last) if previous == Some(signature) => last.push(*v),
+                        _ => refined.push(vec![*v]),
+                    }
+                    previous = Some(signature);
+                }
+            }
+
+            if refined.len() == cells.len() {
+                return refined;
+            }
+            cells = refined;
+        }
+    }
+
+    fn are_twins<V>(&self, u: VertexIndex, v: VertexIndex) -> bool
+    where
+        G: Graph<V>,
+    {
+        self.graph.are_adjacent(u, v) == self.graph.are_adjacent(v, u)
+            && self.graph.are_adjacent(u, u) == self.graph.are_adjacent(v, v)
+            && self.graph.vertex_indices().all(|w| {
+                w == u
+                    || w == v
+                    || (self.graph.are_adjacent(u, w) == self.graph.are_adjacent(v, w)
+                        && self.graph.are_adjacent(w, u) == self.graph.are_adjacent(w, v))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::adjacency_matrix::undirected::UndirectedGraph;
+
+    fn graph(size: usize, edges: &[(usize, usize)]) -> UndirectedGraph<()> {
+        UndirectedGraph::from_edges(
+            &edges
+                .iter()
+                .map(|&(u, v)| (VertexIndex { index: u }, VertexIndex { index: v }))
+                .collect::<Vec<_>>(),
+            &vec![(); size],
+        )
+    }
+
+    #[test]
+    fn isomorphic_graphs_are_equal() {
+        // Path 0-1-2-3 with different labels
+        assert_eq!(
+            canonical_form(&graph(4, &[(0, 1), (1, 2), (2, 3)])),
+            canonical_form(&graph(4, &[(2, 0), (0, 3), (3, 1)]))
+        );
+
+        // Triangle with a tail
+        assert_eq!(
+            canonical_form(&graph(4, &[(0, 1), (1, 2), (2, 0), (2, 3)])),
+            canonical_form(&graph(4, &[(3, 1), (1, 0), (0, 3), (0, 2)]))
+        );
+
+        // Cycle C6 and two triangles have the same degrees, but are not isomorphic
+        assert_ne!(
+            canonical_form(&graph(6, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)])),
+            canonical_form(&graph(6, &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)]))
+        );
+    }
+
+    #[test]
+    fn vertex_values_are_preserved() {
+        let lhs = UndirectedGraph::from_edges(
+            &[(VertexIndex { index: 0 }, VertexIndex { index: 1 })],
+            &[true, false, false],
+        );
+        let rhs = UndirectedGraph::from_edges(
+            &[(VertexIndex { index: 2 }, VertexIndex { index: 1 })],
+            &[false, false, true],
+        );
+        let other = UndirectedGraph::from_edges(
+            &[(VertexIndex { index: 2 }, VertexIndex { index: 1 })],
+            &[true, false, false],
+        );
+        assert_eq!(canonical_form(&lhs), canonical_form(&rhs));
+        assert_ne!(canonical_form(&lhs), canonical_form(&other));
+    }
+}
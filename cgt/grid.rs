@@ -149,6 +149,96 @@ pub trait FiniteGrid: Grid + Sized {
     }
 }
 
+/// Symmetry of a rectangle, i.e. an element of the dihedral group of order 8
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Symmetry {
+    /// Leave grid unchanged
+    Identity,
+
+    /// Rotate clockwise by 90 degrees
+    Rotate90,
+
+    /// Rotate by 180 degrees
+    Rotate180,
+
+    /// Rotate clockwise by 270 degrees
+    Rotate270,
+
+    /// Mirror over the vertical axis, i.e. reverse every row
+    VerticalFlip,
+
+    /// Mirror over the horizontal axis, i.e. reverse every column
+    HorizontalFlip,
+
+    /// Mirror over the main diagonal
+    Transpose,
+
+    /// Mirror over the anti-diagonal
+    AntiTranspose,
+}
+
+impl Symmetry {
+    /// All symmetries of a rectangle
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::VerticalFlip,
+        Symmetry::HorizontalFlip,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    /// Check if symmetry maps rows to columns
+    pub const fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Symmetry::Rotate90
+                | Symmetry::Rotate270
+                | Symmetry::Transpose
+                | Symmetry::AntiTranspose
+        )
+    }
+
+    /// Apply symmetry to a grid
+    pub fn apply<G>(self, grid: &G) -> G
+    where
+        G: FiniteGrid,
+        G::Item: Copy,
+    {
+        let (width, height) = (grid.width(), grid.height());
+        if width == 0 || height == 0 {
+            return G::zero_size();
+        }
+
+        let (new_width, new_height) = if self.swaps_axes() {
+            (height, width)
+        } else {
+            (width, height)
+        };
+        let mut result = G::filled(new_width, new_height, grid.get(0, 0))
+            .expect("transformed grid has the same number of tiles");
+        for y in 0..height {
+            for x in 0..width {
+                let (new_x, new_y) = match self {
+                    Symmetry::Identity => (x, y),
+                    Symmetry::Rotate90 => (height - y - 1, x),
+                    Symmetry::Rotate180 => (width - x - 1, height - y - 1),
+                    Symmetry::Rotate270 => (y, width - x - 1),
+                    Symmetry::VerticalFlip => (width - x - 1, y),
+                    Symmetry::HorizontalFlip => (x, height - y - 1),
+                    Symmetry::Transpose => (y, x),
+                    Symmetry::AntiTranspose => (height - y - 1, width - x - 1),
+                };
+                result.set(new_x, new_y, grid.get(x, y));
+            }
+        }
+        result
+    }
+}
+
 /// Grid tiles that are representable as a single character, other than `'|'`
 pub trait CharTile: Sized {
     /// Convert tile to `char`
@@ -202,12 +292,11 @@ impl BitTile for bool {
 
 // TODO: SVG tile
 
-// TODO: Use grid of bools
 /// Breath first search
 #[inline]
 fn bfs<G, T>(
     grid: &G,
-    visited: &mut G,
+    visited: &mut [bool],
     x: u8,
     y: u8,
     mut is_non_blocking: impl FnMut(T) -> bool,
@@ -223,9 +312,9 @@ where
     let mut q: VecDeque<(u8, u8)> =
         VecDeque::with_capacity(grid.width() as usize * grid.height() as usize);
     q.push_back((x, y));
+    visited[y as usize * grid.width() as usize + x as usize] = true;
 
     while let Some((qx, qy)) = q.pop_front() {
-        visited.set(qx, qy, blocking_tile);
         new_grid.set(qx, qy, grid.get(qx, qy));
 
         for (dx, dy) in directions {
@@ -237,8 +326,9 @@ where
                 && ly >= 0
                 && ly < (grid.height() as i32)
                 && is_non_blocking(grid.get(lx as u8, ly as u8))
-                && is_non_blocking(visited.get(lx as u8, ly as u8))
+                && !visited[ly as usize * grid.width() as usize + lx as usize]
             {
+                visited[ly as usize * grid.width() as usize + lx as usize] = true;
                 q.push_back((lx as u8, ly as u8));
             }
         }
@@ -258,13 +348,14 @@ where
     T: Copy + Default,
     G: Grid<Item = T> + FiniteGrid,
 {
-    let mut visited: G = G::filled(grid.width(), grid.height(), T::default())
-        .expect("unreachable: grid with this size already exists");
+    let mut visited = vec![false; grid.width() as usize * grid.height() as usize];
     let mut ds = Vec::new();
 
     for y in 0..grid.height() {
         for x in 0..grid.width() {
-            if is_non_blocking(grid.get(x, y)) && is_non_blocking(visited.get(x, y)) {
+            if is_non_blocking(grid.get(x, y))
+                && !visited[y as usize * grid.width() as usize + x as usize]
+            {
                 ds.push(bfs(
                     grid,
                     &mut visited,
@@ -411,3 +502,31 @@ where
     }
     new_grid
 }
+
+#[test]
+fn symmetries_work() {
+    use small_bit_grid::SmallBitGrid;
+    use std::str::FromStr;
+
+    let grid = SmallBitGrid::<bool>::from_str("#..|##.").unwrap();
+    assert_eq!(Symmetry::Rotate90.apply(&grid), grid.rotate());
+    assert_eq!(Symmetry::Rotate180.apply(&grid), grid.rotate().rotate());
+    assert_eq!(
+        Symmetry::Rotate270.apply(&grid),
+        grid.rotate().rotate().rotate()
+    );
+    assert_eq!(Symmetry::VerticalFlip.apply(&grid), grid.vertical_flip());
+    assert_eq!(
+        Symmetry::HorizontalFlip.apply(&grid),
+        grid.horizontal_flip()
+    );
+    assert_eq!(
+        Symmetry::Transpose.apply(&grid),
+        grid.rotate().vertical_flip()
+    );
+    assert_eq!(
+        Symmetry::AntiTranspose.apply(&grid),
+        grid.rotate().horizontal_flip()
+    );
+    assert_eq!(Symmetry::Transpose.apply(&grid).to_string(), "##|.#|..");
+}
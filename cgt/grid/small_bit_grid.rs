@@ -1,6 +1,9 @@
 //! Grid with up to 64 tiles holding a single bit of information.
 
-use crate::grid::{BitTile, CharTile, FiniteGrid, Grid};
+use crate::{
+    encoding::Encode,
+    grid::{BitTile, CharTile, FiniteGrid, Grid},
+};
 use std::{fmt::Display, marker::PhantomData, str::FromStr};
 
 /// Internal representation of a grid
@@ -58,7 +61,10 @@ where
             width,
             height,
             grid: if value.tile_to_bool() {
+                // Keep bits outside the grid unset, so equal grids have equal representation
                 GridBits::MAX
+                    .checked_shr(GridBits::BITS - width as u32 * height as u32)
+                    .unwrap_or(0)
             } else {
                 0
             },
@@ -223,6 +229,23 @@ where
     }
 }
 
+impl<T> Encode for SmallBitGrid<T>
+where
+    T: BitTile + Copy,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.width.encode(buf);
+        self.height.encode(buf);
+        self.grid.encode(buf);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        let width = u8::decode(input)?;
+        let height = u8::decode(input)?;
+        Self::from_number(width, height, GridBits::decode(input)?)
+    }
+}
+
 /// Reverse of [`bits_to_arr`]
 ///
 /// # Panics
@@ -303,6 +326,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn filled_equals_parsed() {
+        assert_eq!(
+            SmallBitGrid::<bool>::filled(2, 3, true).unwrap(),
+            SmallBitGrid::<bool>::parse("##|##|##").unwrap()
+        );
+        assert_eq!(
+            SmallBitGrid::<bool>::filled(8, 8, true).unwrap(),
+            SmallBitGrid::<bool>::parse(
+                "########|########|########|########|########|########|########|########"
+            )
+            .unwrap()
+        );
+    }
+
     #[should_panic]
     #[test]
     fn parse_invalid_char() {
@@ -1,6 +1,9 @@
 //! Grid with arbitrary finite size
 
-use crate::grid::{FiniteGrid, Grid};
+use crate::{
+    encoding::Encode,
+    grid::{CharTile, FiniteGrid, Grid},
+};
 
 /// Grid with arbitrary finite size
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -54,3 +57,29 @@ where
         }
     }
 }
+
+impl<T> Encode for VecGrid<T>
+where
+    T: CharTile + Copy,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.width.encode(buf);
+        self.height.encode(buf);
+        for tile in &self.grid {
+            tile.tile_to_char().encode(buf);
+        }
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        let width = u8::decode(input)?;
+        let height = u8::decode(input)?;
+        let grid = (0..width as usize * height as usize)
+            .map(|_| T::char_to_tile(char::decode(input)?))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            width,
+            height,
+            grid,
+        })
+    }
+}
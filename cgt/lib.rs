@@ -10,6 +10,7 @@
 #![warn(missing_copy_implementations)]
 
 pub mod drawing;
+pub mod encoding;
 pub mod genetic_algorithm;
 pub mod graph;
 pub mod grid;
@@ -1,3 +1,4 @@
 //! Loopy games - normal play
 
 pub mod impartial;
+pub mod partizan;
@@ -1,4 +1,5 @@
 //! Loopy games
 
 pub mod games;
+pub mod loopy_impartial_game;
 pub mod vertex;
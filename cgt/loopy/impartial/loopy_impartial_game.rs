@@ -0,0 +1,204 @@
+//! Loopy impartial game - both players have the same moves, and play may return to a previous position
+
+use crate::{
+    graph::Graph,
+    loopy::impartial::vertex::{UnresolvedVertex, Vertex},
+    numeric::nimber::Nimber,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// Loopy impartial game
+pub trait LoopyImpartialGame: Sized + Clone + Eq + Hash {
+    /// Get a list of moves from the position
+    fn moves(&self) -> Vec<Self>;
+
+    /// Calculate the generalized Sprague-Grundy value of the position
+    fn sprague_grundy(&self) -> Vertex {
+        let mut positions = HashMap::new();
+        let mut successors = Vec::new();
+        let mut queue = VecDeque::new();
+
+        positions.insert(self.clone(), 0);
+        successors.push(Vec::new());
+        queue.push_back(self.clone());
+        while let Some(position) = queue.pop_front() {
+            let idx = positions[&position];
+            for m in position.moves() {
+                let next_idx = *positions.entry(m.clone()).or_insert_with(|| {
+                    successors.push(Vec::new());
+                    queue.push_back(m);
+                    successors.len() - 1
+                });
+                successors[idx].push(next_idx);
+            }
+        }
+
+        solve(&successors).swap_remove(0)
+    }
+}
+
+/// Calculate generalized Sprague-Grundy values of every vertex in the game graph, where edges
+/// are moves.
+pub fn sprague_grundy<V, G>(graph: &G) -> Vec<Vertex>
+where
+    G: Graph<V>,
+{
+    let successors = graph
+        .vertex_indices()
+        .map(|v| graph.adjacent_to(v).map(|u| u.index).collect())
+        .collect::<Vec<Vec<usize>>>();
+    solve(&successors)
+}
+
+/// Smith's labelling algorithm, as formulated by Fraenkel and Yesha
+fn solve(successors: &[Vec<usize>]) -> Vec<Vertex> {
+    let mut graph = vec![UnresolvedVertex::Unresolved; successors.len()];
+    let has_value = |graph: &[UnresolvedVertex], v: usize, value: Nimber| {
+        successors[v]
+            .iter()
+            .any(|&u| graph[u] == UnresolvedVertex::Resolved(Vertex::Value(value)))
+    };
+
+    let mut value = Nimber::new(0);
+    while graph.contains(&UnresolvedVertex::Unresolved) {
+        // Vertex gets current value if it cannot move to it, and every move to a vertex that
+        // is not finite can be reversed to the current value
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for v in 0..graph.len() {
+                if graph[v] != UnresolvedVertex::Unresolved || has_value(&graph, v, value) {
+                    continue;
+                }
+                let reversible = successors[v].iter().all(|&u| {
+                    matches!(graph[u], UnresolvedVertex::Resolved(Vertex::Value(_)))
+                        || has_value(&graph, u, value)
+                });
+                if reversible {
+                    graph[v] = UnresolvedVertex::Resolved(Vertex::Value(value));
+                    changed = true;
+                }
+            }
+        }
+
+        // Vertices that cannot move to the current value will never be finite
+        for v in 0..graph.len() {
+            if graph[v] == UnresolvedVertex::Unresolved && !has_value(&graph, v, value) {
+                graph[v] = UnresolvedVertex::Resolved(Vertex::Loop(Vec::new()));
+            }
+        }
+
+        value = Nimber::new(value.value() + 1);
+    }
+
+    let values = graph
+        .iter()
+        .map(|v| match v {
+            UnresolvedVertex::Resolved(v) => v.clone(),
+            UnresolvedVertex::Unresolved => unreachable!("All vertices should be resolved"),
+        })
+        .collect::<Vec<_>>();
+
+    values
+        .iter()
+        .enumerate()
+        .map(|(v, value)| match value {
+            Vertex::Value(_) => value.clone(),
+            Vertex::Loop(_) => {
+                let mut infinities = Vec::new();
+                for &u in &successors[v] {
+                    if let Vertex::Value(g) = values[u]
+                        && !infinities.contains(&g)
+                    {
+                        infinities.push(g);
+                    }
+                }
+                infinities.sort_unstable();
+                Vertex::Loop(infinities)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::{VertexIndex, adjacency_matrix::directed::DirectedGraph},
+        loopy::impartial::{games::wind_up::WindUp, vertex::Outcome},
+    };
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Modular {
+        position: u32,
+        n: u32,
+        subtraction_set: Vec<u32>,
+    }
+
+    impl LoopyImpartialGame for Modular {
+        fn moves(&self) -> Vec<Self> {
+            if self.position == 0 {
+                return Vec::new();
+            }
+            self.subtraction_set
+                .iter()
+                .map(|m| Self {
+                    position: (self.position + self.n - m % self.n) % self.n,
+                    ..self.clone()
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn agrees_with_wind_up() {
+        for subtraction_set in [vec![1, 3], vec![6, 7], vec![2, 5, 6]] {
+            let n = 25;
+            let wind_up = WindUp::new_using_graph(n, subtraction_set.clone());
+            for position in 0..n {
+                let game = Modular {
+                    position,
+                    n,
+                    subtraction_set: subtraction_set.clone(),
+                };
+                assert_eq!(&game.sprague_grundy(), &wind_up.graph()[position as usize]);
+            }
+        }
+    }
+
+    #[test]
+    fn directed_graph() {
+        // a <-> b -> c
+        let graph = DirectedGraph::from_edges(
+            &[
+                (VertexIndex { index: 0 }, VertexIndex { index: 1 }),
+                (VertexIndex { index: 1 }, VertexIndex { index: 0 }),
+                (VertexIndex { index: 1 }, VertexIndex { index: 2 }),
+            ],
+            &[(); 3],
+        );
+        let values = sprague_grundy(&graph);
+        assert_eq!(values[0], Vertex::Value(Nimber::new(0)));
+        assert_eq!(values[1], Vertex::Value(Nimber::new(1)));
+        assert_eq!(values[2], Vertex::Value(Nimber::new(0)));
+
+        // a <-> b, b -> c -> d
+        let graph = DirectedGraph::from_edges(
+            &[
+                (VertexIndex { index: 0 }, VertexIndex { index: 1 }),
+                (VertexIndex { index: 1 }, VertexIndex { index: 0 }),
+                (VertexIndex { index: 1 }, VertexIndex { index: 2 }),
+                (VertexIndex { index: 2 }, VertexIndex { index: 3 }),
+            ],
+            &[(); 4],
+        );
+        let values = sprague_grundy(&graph);
+        assert_eq!(values[0].to_string(), "∞");
+        assert_eq!(values[1].to_string(), "∞(*)");
+        assert_eq!(values[0].outcome(), Outcome::D);
+        assert_eq!((&values[1] + &values[2]).outcome(), Outcome::N);
+    }
+}
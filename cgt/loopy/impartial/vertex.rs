@@ -1,6 +1,7 @@
 //! Loopy game graph vertex
 
 use crate::{display, numeric::nimber::Nimber};
+use auto_ops::impl_op_ex;
 use std::fmt::Display;
 
 /// Vertex set used during graph orbiting
@@ -40,9 +41,82 @@ impl Display for Vertex {
     }
 }
 
+/// Outcome of a loopy impartial game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Outcome {
+    /// First player wins
+    N,
+
+    /// Second player wins
+    P,
+
+    /// Game is a draw, i.e. neither player can force a win
+    D,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::N => write!(f, "N"),
+            Self::P => write!(f, "P"),
+            Self::D => write!(f, "D"),
+        }
+    }
+}
+
+impl Vertex {
+    /// Outcome of a position with this value. Infinite position is a first player win only if
+    /// it can move to a zero.
+    pub fn outcome(&self) -> Outcome {
+        match self {
+            Self::Value(n) if n.value() == 0 => Outcome::P,
+            Self::Value(_) => Outcome::N,
+            Self::Loop(infs) if infs.contains(&Nimber::new(0)) => Outcome::N,
+            Self::Loop(_) => Outcome::D,
+        }
+    }
+
+    /// Value of a sum of two positions
+    ///
+    /// Sum of two infinite positions is `∞` without any subscripts, not a sum of their
+    /// subscripts. It is a draw regardless of the positions, but it cannot be used to recover
+    /// values of its options.
+    pub fn construct_sum(lhs: &Self, rhs: &Self) -> Self {
+        match (lhs, rhs) {
+            (Self::Value(lhs), Self::Value(rhs)) => Self::Value(lhs + rhs),
+            (Self::Value(n), Self::Loop(infs)) | (Self::Loop(infs), Self::Value(n)) => {
+                let mut infs = infs.iter().map(|inf| inf + n).collect::<Vec<_>>();
+                infs.sort_unstable();
+                Self::Loop(infs)
+            }
+            // Every move leaves at least one infinite component, so no option has a finite
+            // value and there is nothing to escape to
+            (Self::Loop(_), Self::Loop(_)) => Self::Loop(Vec::new()),
+        }
+    }
+}
+
+impl_op_ex!(+|lhs: &Vertex, rhs: &Vertex| -> Vertex { Vertex::construct_sum(lhs, rhs) });
+impl_op_ex!(+=|lhs: &mut Vertex, rhs: &Vertex| { *lhs = Vertex::construct_sum(lhs, rhs) });
+
 impl UnresolvedVertex {
     /// Check if vertex is a finite zero
     pub const fn is_zero(&self) -> bool {
         matches!(self, Self::Resolved(Vertex::Value(val)) if val.value() == 0)
     }
 }
+
+#[test]
+fn sum_works() {
+    let zero = Vertex::Value(Nimber::new(0));
+    let star = Vertex::Value(Nimber::new(1));
+    let inf = Vertex::Loop(vec![Nimber::new(0), Nimber::new(2)]);
+
+    assert_eq!((&star + &star).outcome(), Outcome::P);
+    assert_eq!((&zero + &inf).outcome(), Outcome::N);
+    assert_eq!((&star + &inf).to_string(), "∞(*, *3)");
+    assert_eq!((&star + &inf).outcome(), Outcome::D);
+    assert_eq!(&inf + &inf, Vertex::Loop(vec![]));
+    assert_eq!((&inf + &inf).outcome(), Outcome::D);
+}
@@ -0,0 +1,8 @@
+//! Loopy partizan games
+//!
+//! Every loopy game is described by its onside and offside, i.e. stoppers obtained by treating
+//! infinite play as a win for Left and Right respectively.
+
+pub mod game_graph;
+pub mod sides;
+pub mod stopper;
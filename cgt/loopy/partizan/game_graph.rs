@@ -0,0 +1,585 @@
+//! Finite graph of a loopy partizan game
+
+use crate::{
+    graph::VertexIndex, short::partizan::canonical_form::CanonicalForm, total::TotalWrapper,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Finite graph of a loopy partizan game with separate Left and Right moves
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct GameGraph {
+    left_edges: Vec<Vec<VertexIndex>>,
+    right_edges: Vec<Vec<VertexIndex>>,
+}
+
+impl GameGraph {
+    /// Create graph without any vertices
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            left_edges: Vec::new(),
+            right_edges: Vec::new(),
+        }
+    }
+
+    /// Number of vertices in the graph
+    #[inline]
+    pub const fn size(&self) -> usize {
+        self.left_edges.len()
+    }
+
+    /// Iterator over all vertices in the graph
+    pub fn vertex_indices(&self) -> impl Iterator<Item = VertexIndex> + use<> {
+        (0..self.size()).map(|index| VertexIndex { index })
+    }
+
+    /// Add new vertex without any moves
+    pub fn add_vertex(&mut self) -> VertexIndex {
+        let index = self.size();
+        self.left_edges.push(Vec::new());
+        self.right_edges.push(Vec::new());
+        VertexIndex { index }
+    }
+
+    /// Add Left move between two vertices. Adding existing move does nothing
+    pub fn add_left_edge(&mut self, from: VertexIndex, to: VertexIndex) {
+        if !self.left_edges[from.index].contains(&to) {
+            self.left_edges[from.index].push(to);
+        }
+    }
+
+    /// Add Right move between two vertices. Adding existing move does nothing
+    pub fn add_right_edge(&mut self, from: VertexIndex, to: VertexIndex) {
+        if !self.right_edges[from.index].contains(&to) {
+            self.right_edges[from.index].push(to);
+        }
+    }
+
+    /// Left moves from a given vertex
+    #[inline]
+    pub fn left_edges(&self, vertex: VertexIndex) -> &[VertexIndex] {
+        &self.left_edges[vertex.index]
+    }
+
+    /// Right moves from a given vertex
+    #[inline]
+    pub fn right_edges(&self, vertex: VertexIndex) -> &[VertexIndex] {
+        &self.right_edges[vertex.index]
+    }
+
+    /// Graph of the negative game, i.e. with Left and Right moves swapped
+    #[must_use]
+    pub fn negative(&self) -> Self {
+        Self {
+            left_edges: self.right_edges.clone(),
+            right_edges: self.left_edges.clone(),
+        }
+    }
+
+    /// Graph of a disjunctive sum of two positions. Only positions reachable from the sum of
+    /// starting positions are included.
+    pub fn sum(
+        lhs: &Self,
+        lhs_start: VertexIndex,
+        rhs: &Self,
+        rhs_start: VertexIndex,
+    ) -> (Self, VertexIndex) {
+        let mut graph = Self::new();
+        let mut vertices = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        let start = graph.add_vertex();
+        vertices.insert((lhs_start, rhs_start), start);
+        queue.push_back((lhs_start, rhs_start));
+
+        while let Some((l, r)) = queue.pop_front() {
+            let from = vertices[&(l, r)];
+
+            let left_moves = lhs.left_edges(l).iter().map(|&l_move| (l_move, r));
+            let left_moves = left_moves.chain(rhs.left_edges(r).iter().map(|&r_move| (l, r_move)));
+            for position in left_moves {
+                let to = *vertices.entry(position).or_insert_with(|| {
+                    queue.push_back(position);
+                    graph.add_vertex()
+                });
+                graph.add_left_edge(from, to);
+            }
+
+            let right_moves = lhs.right_edges(l).iter().map(|&l_move| (l_move, r));
+            let right_moves =
+                right_moves.chain(rhs.right_edges(r).iter().map(|&r_move| (l, r_move)));
+            for position in right_moves {
+                let to = *vertices.entry(position).or_insert_with(|| {
+                    queue.push_back(position);
+                    graph.add_vertex()
+                });
+                graph.add_right_edge(from, to);
+            }
+        }
+
+        (graph, start)
+    }
+
+    /// Graph of a short game
+    pub fn from_canonical_form(game: &CanonicalForm) -> (Self, VertexIndex) {
+        let mut graph = Self::new();
+        let start = graph.add_canonical_form(game);
+        (graph, start)
+    }
+
+    /// Add vertices of a short game to the graph and return its starting vertex
+    pub fn add_canonical_form(&mut self, game: &CanonicalForm) -> VertexIndex {
+        self.add_canonical_form_with(game, &mut HashMap::new())
+    }
+
+    fn add_canonical_form_with(
+        &mut self,
+        game: &CanonicalForm,
+        seen: &mut HashMap<TotalWrapper<CanonicalForm>, VertexIndex>,
+    ) -> VertexIndex {
+        if let Some(&vertex) = seen.get(TotalWrapper::from_ref(game)) {
+            return vertex;
+        }
+
+        let vertex = self.add_vertex();
+        seen.insert(TotalWrapper::new(game.clone()), vertex);
+        for left_move in game.left_moves() {
+            let left_move = self.add_canonical_form_with(&left_move, seen);
+            self.add_left_edge(vertex, left_move);
+        }
+        for right_move in game.right_moves() {
+            let right_move = self.add_canonical_form_with(&right_move, seen);
+            self.add_right_edge(vertex, right_move);
+        }
+        vertex
+    }
+
+    /// Alternating play graph: vertex `2 * v` is `v` with Left to move, `2 * v + 1` with Right to move
+    fn alternating_successors(&self, state: usize) -> impl Iterator<Item = usize> + '_ {
+        let vertex = VertexIndex { index: state / 2 };
+        let (edges, next_turn) = if state.is_multiple_of(2) {
+            (self.left_edges(vertex), 1)
+        } else {
+            (self.right_edges(vertex), 0)
+        };
+        edges.iter().map(move |to| 2 * to.index + next_turn)
+    }
+
+    /// Check which vertices are stoppers, i.e. no infinite alternating play is possible from them
+    pub fn stoppers(&self) -> Vec<bool> {
+        let states = 2 * self.size();
+        let mut predecessors = vec![Vec::new(); states];
+        let mut remaining = vec![0usize; states];
+        for (state, remaining) in remaining.iter_mut().enumerate() {
+            for successor in self.alternating_successors(state) {
+                predecessors[successor].push(state);
+                *remaining += 1;
+            }
+        }
+
+        // States that cannot reach a cycle are peeled off from the terminal ones
+        let mut finite = vec![false; states];
+        let mut queue = (0..states)
+            .filter(|&state| remaining[state] == 0)
+            .collect::<VecDeque<_>>();
+        while let Some(state) = queue.pop_front() {
+            finite[state] = true;
+            for &predecessor in &predecessors[state] {
+                remaining[predecessor] -= 1;
+                if remaining[predecessor] == 0 {
+                    queue.push_back(predecessor);
+                }
+            }
+        }
+
+        (0..self.size())
+            .map(|vertex| finite[2 * vertex] && finite[2 * vertex + 1])
+            .collect()
+    }
+
+    /// Check if infinite alternating play is impossible from a given vertex
+    pub fn is_stopper(&self, vertex: VertexIndex) -> bool {
+        self.stoppers()[vertex.index]
+    }
+
+    /// Check which vertices cannot reach a cycle of any kind, i.e. are short games
+    pub fn loopfree(&self) -> Vec<bool> {
+        let mut predecessors = vec![Vec::new(); self.size()];
+        let mut remaining = vec![0usize; self.size()];
+        for vertex in self.vertex_indices() {
+            let moves = self
+                .left_edges(vertex)
+                .iter()
+                .chain(self.right_edges(vertex));
+            for to in moves {
+                predecessors[to.index].push(vertex.index);
+                remaining[vertex.index] += 1;
+            }
+        }
+
+        let mut loopfree = vec![false; self.size()];
+        let mut queue = (0..self.size())
+            .filter(|&vertex| remaining[vertex] == 0)
+            .collect::<VecDeque<_>>();
+        while let Some(vertex) = queue.pop_front() {
+            loopfree[vertex] = true;
+            for &predecessor in &predecessors[vertex] {
+                remaining[predecessor] -= 1;
+                if remaining[predecessor] == 0 {
+                    queue.push_back(predecessor);
+                }
+            }
+        }
+        loopfree
+    }
+
+    /// Copy of the graph with only vertices reachable from `start`, where `start` becomes the
+    /// first vertex
+    pub(crate) fn reachable(&self, start: VertexIndex) -> (Self, VertexIndex) {
+        let mut mapping = vec![None; self.size()];
+        let mut order = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        mapping[start.index] = Some(VertexIndex { index: 0 });
+        while let Some(vertex) = queue.pop_front() {
+            order.push(vertex);
+            for &to in self
+                .left_edges(vertex)
+                .iter()
+                .chain(self.right_edges(vertex))
+            {
+                if mapping[to.index].is_none() {
+                    mapping[to.index] = Some(VertexIndex {
+                        index: order.len() + queue.len(),
+                    });
+                    queue.push_back(to);
+                }
+            }
+        }
+
+        let mut graph = Self::new();
+        for _ in &order {
+            graph.add_vertex();
+        }
+        for (index, &vertex) in order.iter().enumerate() {
+            let from = VertexIndex { index };
+            for to in self.left_edges(vertex) {
+                graph.add_left_edge(from, mapping[to.index].unwrap());
+            }
+            for to in self.right_edges(vertex) {
+                graph.add_right_edge(from, mapping[to.index].unwrap());
+            }
+        }
+
+        (graph, VertexIndex { index: 0 })
+    }
+
+    /// Compute for every pair of vertices `(l, r)` whether Left survives `l - r` when Right
+    /// moves first. Infinite play is won by Left if there were infinitely many moves in `l`.
+    ///
+    /// For stoppers this is exactly `l >= r`. Result is indexed with `l * rhs.size() + r`.
+    pub(crate) fn left_survives(lhs: &Self, rhs: &Self) -> Vec<bool> {
+        let width = rhs.size();
+        let states = 4 * lhs.size() * width;
+        let state = |l: VertexIndex, r: VertexIndex, right_to_move: bool, lhs_moved: bool| {
+            ((l.index * width + r.index) * 2 + right_to_move as usize) * 2 + lhs_moved as usize
+        };
+        let right_to_move = |state: usize| (state / 2) % 2 == 1;
+
+        let mut successors = vec![Vec::new(); states];
+        let mut predecessors = vec![Vec::new(); states];
+        for l in lhs.vertex_indices() {
+            for r in rhs.vertex_indices() {
+                let mut left_moves = Vec::new();
+                left_moves.extend(lhs.left_edges(l).iter().map(|&l| state(l, r, true, true)));
+                left_moves.extend(rhs.right_edges(r).iter().map(|&r| state(l, r, true, false)));
+                let mut right_moves = Vec::new();
+                right_moves.extend(lhs.right_edges(l).iter().map(|&l| state(l, r, false, true)));
+                right_moves.extend(rhs.left_edges(r).iter().map(|&r| state(l, r, false, false)));
+
+                for lhs_moved in [false, true] {
+                    for (right_to_move, moves) in [(false, &left_moves), (true, &right_moves)] {
+                        let from = state(l, r, right_to_move, lhs_moved);
+                        for &to in moves {
+                            predecessors[to].push(from);
+                        }
+                        successors[from].clone_from(moves);
+                    }
+                }
+            }
+        }
+
+        // Buchi game: Left must visit states after a move in `lhs` infinitely often
+        let mut arena = vec![true; states];
+        loop {
+            let target = (0..states)
+                .map(|s| arena[s] && s % 2 == 1 && (right_to_move(s) || !successors[s].is_empty()))
+                .collect::<Vec<_>>();
+            let reaching = attractor(&successors, &predecessors, &arena, &target, |s| {
+                !right_to_move(s)
+            });
+            let trap = (0..states)
+                .map(|s| arena[s] && !reaching[s])
+                .collect::<Vec<_>>();
+            if !trap.contains(&true) {
+                break;
+            }
+            let lost = attractor(&successors, &predecessors, &arena, &trap, right_to_move);
+            for (in_arena, lost) in arena.iter_mut().zip(lost) {
+                *in_arena &= !lost;
+            }
+        }
+
+        let mut result = Vec::with_capacity(lhs.size() * width);
+        for l in lhs.vertex_indices() {
+            for r in rhs.vertex_indices() {
+                result.push(arena[state(l, r, true, false)]);
+            }
+        }
+        result
+    }
+
+    /// Compute simplified onside of a given vertex using sidling. Infinite play is treated as
+    /// a win for Left, dominated options are eliminated and reversible options are bypassed
+    /// until the graph is a stopper in the simplest form.
+    pub(crate) fn onside(&self, start: VertexIndex) -> (Self, VertexIndex) {
+        let (mut graph, start) = self.reachable(start);
+        let mut seen = HashSet::new();
+        while seen.insert(graph.clone()) {
+            let size = graph.size();
+            let survives = Self::left_survives(&graph, &graph);
+            let geq = |lhs: VertexIndex, rhs: VertexIndex| survives[lhs.index * size + rhs.index];
+
+            if !graph.eliminate_dominated(geq)
+                && !graph.bypass_reversible(geq)
+                && !graph.remove_alternating_right_edges()
+            {
+                break;
+            }
+        }
+        graph.remove_alternating_right_edges();
+        graph.fuse(start)
+    }
+
+    fn eliminate_dominated(&mut self, geq: impl Fn(VertexIndex, VertexIndex) -> bool) -> bool {
+        fn undominated(
+            moves: &[VertexIndex],
+            better: impl Fn(VertexIndex, VertexIndex) -> bool,
+        ) -> Vec<VertexIndex> {
+            moves
+                .iter()
+                .enumerate()
+                .filter(|&(i, &m)| {
+                    !moves.iter().enumerate().any(|(j, &other)| {
+                        i != j && better(other, m) && (!better(m, other) || j < i)
+                    })
+                })
+                .map(|(_, &m)| m)
+                .collect()
+        }
+
+        let mut changed = false;
+        for vertex in self.vertex_indices() {
+            let left = undominated(self.left_edges(vertex), &geq);
+            let right = undominated(self.right_edges(vertex), |lhs, rhs| geq(rhs, lhs));
+            changed |= left.len() != self.left_edges[vertex.index].len();
+            changed |= right.len() != self.right_edges[vertex.index].len();
+            self.left_edges[vertex.index] = left;
+            self.right_edges[vertex.index] = right;
+        }
+        changed
+    }
+
+    fn bypass_reversible(&mut self, geq: impl Fn(VertexIndex, VertexIndex) -> bool) -> bool {
+        let mut left_edges = Vec::with_capacity(self.size());
+        let mut right_edges = Vec::with_capacity(self.size());
+        for vertex in self.vertex_indices() {
+            let mut left = Vec::new();
+            for &left_move in self.left_edges(vertex) {
+                // Left option is reversible if Right can respond with something at most `vertex`
+                let replacement = bypass(left_move, |option| {
+                    self.right_edges(option)
+                        .iter()
+                        .find(|&&response| geq(vertex, response))
+                        .map(|&response| self.left_edges(response))
+                });
+                // Infinite chain of reversals favors Left so the option is kept as is
+                for m in replacement.unwrap_or_else(|| vec![left_move]) {
+                    if !left.contains(&m) {
+                        left.push(m);
+                    }
+                }
+            }
+
+            let mut right = Vec::new();
+            for &right_move in self.right_edges(vertex) {
+                // Infinite chain of reversals favors Left so these options are dropped
+                let replacement = bypass(right_move, |option| {
+                    self.left_edges(option)
+                        .iter()
+                        .find(|&&response| geq(response, vertex))
+                        .map(|&response| self.right_edges(response))
+                });
+                for m in replacement.unwrap_or_default() {
+                    if !right.contains(&m) {
+                        right.push(m);
+                    }
+                }
+            }
+
+            left_edges.push(left);
+            right_edges.push(right);
+        }
+
+        let changed = left_edges != self.left_edges || right_edges != self.right_edges;
+        self.left_edges = left_edges;
+        self.right_edges = right_edges;
+        changed
+    }
+
+    /// Remove Right moves that lie on a cycle of alternating play
+    fn remove_alternating_right_edges(&mut self) -> bool {
+        let mut changed = false;
+        for vertex in self.vertex_indices() {
+            let from = 2 * vertex.index + 1;
+            let right = self.right_edges[vertex.index]
+                .iter()
+                .copied()
+                .filter(|to| {
+                    let mut visited = HashSet::from([2 * to.index]);
+                    let mut stack = vec![2 * to.index];
+                    while let Some(state) = stack.pop() {
+                        if state == from {
+                            return false;
+                        }
+                        for successor in self.alternating_successors(state) {
+                            if visited.insert(successor) {
+                                stack.push(successor);
+                            }
+                        }
+                    }
+                    true
+                })
+                .collect::<Vec<_>>();
+            changed |= right.len() != self.right_edges[vertex.index].len();
+            self.right_edges[vertex.index] = right;
+        }
+        changed
+    }
+
+    /// Merge equal vertices of a stopper and drop unreachable ones
+    fn fuse(&self, start: VertexIndex) -> (Self, VertexIndex) {
+        let size = self.size();
+        let survives = Self::left_survives(self, self);
+        let representative = (0..size)
+            .map(|v| {
+                (0..size)
+                    .find(|&u| survives[u * size + v] && survives[v * size + u])
+                    .map_or(VertexIndex { index: v }, |index| VertexIndex { index })
+            })
+            .collect::<Vec<_>>();
+
+        let mut graph = Self::new();
+        for _ in 0..size {
+            graph.add_vertex();
+        }
+        for vertex in self.vertex_indices() {
+            let from = representative[vertex.index];
+            for to in self.left_edges(vertex) {
+                graph.add_left_edge(from, representative[to.index]);
+            }
+            for to in self.right_edges(vertex) {
+                graph.add_right_edge(from, representative[to.index]);
+            }
+        }
+        graph.reachable(representative[start.index])
+    }
+}
+
+/// Follow chain of reversible options. Returns `None` if the chain loops.
+fn bypass<'a>(
+    option: VertexIndex,
+    reversal: impl Fn(VertexIndex) -> Option<&'a [VertexIndex]>,
+) -> Option<Vec<VertexIndex>> {
+    let mut result = Vec::new();
+    let mut visited = HashSet::from([option]);
+    let mut stack = vec![option];
+    let mut looped = false;
+    while let Some(option) = stack.pop() {
+        match reversal(option) {
+            None => result.push(option),
+            Some(replacements) => {
+                for &replacement in replacements {
+                    if visited.insert(replacement) {
+                        stack.push(replacement);
+                    } else if reversal(replacement).is_some() {
+                        looped = true;
+                    }
+                }
+            }
+        }
+    }
+    (!looped).then_some(result)
+}
+
+/// Set of states from which `owner` can force reaching `target` while staying in `arena`
+fn attractor(
+    successors: &[Vec<usize>],
+    predecessors: &[Vec<usize>],
+    arena: &[bool],
+    target: &[bool],
+    owner: impl Fn(usize) -> bool,
+) -> Vec<bool> {
+    let mut remaining = successors
+        .iter()
+        .map(|moves| moves.iter().filter(|&&to| arena[to]).count())
+        .collect::<Vec<_>>();
+    let mut attracted = vec![false; successors.len()];
+    let mut queue = VecDeque::new();
+    for state in 0..successors.len() {
+        // Opponent without moves loses immediately
+        if arena[state] && (target[state] || (!owner(state) && remaining[state] == 0)) {
+            attracted[state] = true;
+            queue.push_back(state);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        for &predecessor in &predecessors[state] {
+            if !arena[predecessor] || attracted[predecessor] {
+                continue;
+            }
+            if !owner(predecessor) {
+                remaining[predecessor] -= 1;
+                if remaining[predecessor] != 0 {
+                    continue;
+                }
+            }
+            attracted[predecessor] = true;
+            queue.push_back(predecessor);
+        }
+    }
+    attracted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_stoppers() {
+        // dud = {dud | dud}, on = {on | }, over = {0 | over}
+        let mut graph = GameGraph::new();
+        let dud = graph.add_vertex();
+        graph.add_left_edge(dud, dud);
+        graph.add_right_edge(dud, dud);
+        let on = graph.add_vertex();
+        graph.add_left_edge(on, on);
+        let zero = graph.add_vertex();
+        let over = graph.add_vertex();
+        graph.add_left_edge(over, zero);
+        graph.add_right_edge(over, over);
+
+        assert_eq!(graph.stoppers(), vec![false, true, true, true]);
+        assert_eq!(graph.loopfree(), vec![false, false, true, false]);
+    }
+}
@@ -0,0 +1,238 @@
+//! Loopy game values represented by their onside and offside
+
+use crate::{
+    graph::VertexIndex,
+    loopy::partizan::{game_graph::GameGraph, stopper::Stopper},
+    short::partizan::canonical_form::CanonicalForm,
+};
+use auto_ops::impl_op_ex;
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    iter::Sum,
+};
+
+/// Value of a loopy game given by its onside and offside
+#[derive(Debug, Clone)]
+pub struct Sides {
+    onside: Stopper,
+    offside: Stopper,
+}
+
+impl Sides {
+    /// Compute sides of a vertex in a game graph
+    pub fn new(graph: &GameGraph, start: VertexIndex) -> Self {
+        Self {
+            onside: Stopper::onside_of(graph, start),
+            offside: Stopper::offside_of(graph, start),
+        }
+    }
+
+    /// `dud = {dud|dud}`, i.e. `on & off`
+    pub fn dud() -> Self {
+        Self {
+            onside: Stopper::on(),
+            offside: Stopper::off(),
+        }
+    }
+
+    /// Onside of the game
+    #[inline]
+    pub const fn onside(&self) -> &Stopper {
+        &self.onside
+    }
+
+    /// Offside of the game
+    #[inline]
+    pub const fn offside(&self) -> &Stopper {
+        &self.offside
+    }
+
+    /// Check if the game is equal to a stopper, i.e. both sides are equal
+    pub fn is_stopper(&self) -> bool {
+        self.onside == self.offside
+    }
+
+    /// Check if one game is less than or equal to another
+    pub fn leq(lhs: &Self, rhs: &Self) -> bool {
+        Stopper::leq(&lhs.onside, &rhs.onside) && Stopper::leq(&lhs.offside, &rhs.offside)
+    }
+
+    /// Construct a sum of two games. Onside of the sum is upsum of onsides and offside is
+    /// downsum of offsides.
+    pub fn construct_sum(lhs: &Self, rhs: &Self) -> Self {
+        Self {
+            onside: lhs.onside.upsum(&rhs.onside),
+            offside: lhs.offside.downsum(&rhs.offside),
+        }
+    }
+
+    /// Construct negative of the game. Negation swaps sides.
+    pub fn construct_negative(game: &Self) -> Self {
+        Self {
+            onside: -&game.offside,
+            offside: -&game.onside,
+        }
+    }
+}
+
+impl From<Stopper> for Sides {
+    fn from(stopper: Stopper) -> Self {
+        Self {
+            onside: stopper.clone(),
+            offside: stopper,
+        }
+    }
+}
+
+impl From<CanonicalForm> for Sides {
+    fn from(game: CanonicalForm) -> Self {
+        Self::from(Stopper::from(game))
+    }
+}
+
+impl PartialEq for Sides {
+    fn eq(&self, other: &Self) -> bool {
+        self.onside == other.onside && self.offside == other.offside
+    }
+}
+
+impl PartialOrd for Sides {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (Self::leq(self, other), Self::leq(other, self)) {
+            (true, true) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Less),
+            (false, true) => Some(Ordering::Greater),
+            (false, false) => None,
+        }
+    }
+}
+
+impl_op_ex!(+|g: &Sides, h: &Sides| -> Sides { Sides::construct_sum(g, h) });
+impl_op_ex!(+=|g: &mut Sides, h: &Sides| { *g = Sides::construct_sum(g, h) });
+impl_op_ex!(-|g: &Sides| -> Sides { Sides::construct_negative(g) });
+impl_op_ex!(-|g: &Sides, h: &Sides| -> Sides {
+    Sides::construct_sum(g, &Sides::construct_negative(h))
+});
+impl_op_ex!(-=|g: &mut Sides, h: &Sides| {
+    *g = Sides::construct_sum(g, &Sides::construct_negative(h));
+});
+
+impl Display for Sides {
+    /// Print stoppers as is, other games as `onside & offside`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_stopper() {
+            self.onside.fmt(f)
+        } else if self.onside == Stopper::on() && self.offside == Stopper::off() {
+            write!(f, "dud")
+        } else {
+            write!(f, "{} & {}", self.onside, self.offside)
+        }
+    }
+}
+
+impl Sum for Sides {
+    fn sum<I: Iterator<Item = Sides>>(iter: I) -> Sides {
+        iter.fold(Sides::from(CanonicalForm::new_integer(0)), |acc, v| acc + v)
+    }
+}
+
+impl<'a> Sum<&'a Sides> for Sides {
+    fn sum<I: Iterator<Item = &'a Sides>>(iter: I) -> Sides {
+        iter.fold(Sides::from(CanonicalForm::new_integer(0)), |acc, v| acc + v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    macro_rules! short {
+        ($str:expr) => {
+            Sides::from(CanonicalForm::from_str($str).unwrap())
+        };
+    }
+
+    #[test]
+    fn dud_sides() {
+        let mut graph = GameGraph::new();
+        let dud = graph.add_vertex();
+        graph.add_left_edge(dud, dud);
+        graph.add_right_edge(dud, dud);
+
+        let dud = Sides::new(&graph, dud);
+        assert_eq!(dud.to_string(), "dud");
+        assert_eq!(dud.onside().to_string(), "on");
+        assert_eq!(dud.offside().to_string(), "off");
+        assert_eq!(dud, Sides::dud());
+        assert_eq!(dud.partial_cmp(&short!("0")), None);
+    }
+
+    #[test]
+    fn on_plus_off_is_dud() {
+        let on = Sides::from(Stopper::on());
+        let off = Sides::from(Stopper::off());
+        assert_eq!(&on + &off, Sides::dud());
+        assert_eq!((&on + short!("5")).to_string(), "on");
+        assert_eq!(-&on, off);
+        assert_eq!(Sides::dud() + short!("{1|*}"), Sides::dud());
+    }
+
+    #[test]
+    fn short_games_agree() {
+        let games = ["0", "*", "^", "1/2", "{1|-1}", "{2|*}"];
+        for lhs in games {
+            for rhs in games {
+                let cf =
+                    CanonicalForm::from_str(lhs).unwrap() + CanonicalForm::from_str(rhs).unwrap();
+                let sum = short!(lhs) + short!(rhs);
+                assert!(sum.is_stopper());
+                assert_eq!(sum.onside().to_canonical_form(), Some(cf));
+            }
+        }
+    }
+
+    #[test]
+    fn sidling() {
+        // g = {0 | dud, -100}
+        let mut graph = GameGraph::new();
+        let g = graph.add_vertex();
+        let dud = graph.add_vertex();
+        let zero = graph.add_canonical_form(&CanonicalForm::new_integer(0));
+        let minus_hundred = graph.add_canonical_form(&CanonicalForm::new_integer(-100));
+        graph.add_left_edge(dud, dud);
+        graph.add_right_edge(dud, dud);
+        graph.add_left_edge(g, zero);
+        graph.add_right_edge(g, dud);
+        graph.add_right_edge(g, minus_hundred);
+
+        assert_eq!(Sides::new(&graph, g).to_string(), "{0|-100} & {0|off}");
+    }
+
+    #[test]
+    fn sidling_long_cycle() {
+        // a = {10 | b}, b = {c | -10}, c = {5 | d}, d = {a | -20}
+        let mut graph = GameGraph::new();
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let ten = graph.add_canonical_form(&CanonicalForm::new_integer(10));
+        let five = graph.add_canonical_form(&CanonicalForm::new_integer(5));
+        let minus_ten = graph.add_canonical_form(&CanonicalForm::new_integer(-10));
+        let minus_twenty = graph.add_canonical_form(&CanonicalForm::new_integer(-20));
+        graph.add_left_edge(a, ten);
+        graph.add_right_edge(a, b);
+        graph.add_left_edge(b, c);
+        graph.add_right_edge(b, minus_ten);
+        graph.add_left_edge(c, five);
+        graph.add_right_edge(c, d);
+        graph.add_left_edge(d, a);
+        graph.add_right_edge(d, minus_twenty);
+
+        assert!(!graph.is_stopper(a));
+        assert_eq!(Stopper::onside_of(&graph, a).to_string(), "{10|{6|-10}}");
+        assert_eq!(Stopper::onside_of(&graph, c).to_string(), "6");
+    }
+}
@@ -0,0 +1,348 @@
+//! Stoppers - loopy games in which infinite alternating play is impossible
+
+use crate::{
+    display::variable_name, graph::VertexIndex, loopy::partizan::game_graph::GameGraph,
+    short::partizan::canonical_form::CanonicalForm,
+};
+use auto_ops::impl_op_ex;
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Write},
+};
+
+/// Stopper in the simplest form
+#[derive(Debug, Clone)]
+pub struct Stopper {
+    graph: GameGraph,
+    start: VertexIndex,
+}
+
+impl Stopper {
+    /// Construct stopper from a vertex of a game graph. Returns `None` if infinite alternating
+    /// play is possible from that vertex.
+    pub fn new(graph: &GameGraph, start: VertexIndex) -> Option<Self> {
+        graph
+            .is_stopper(start)
+            .then(|| Self::onside_of(graph, start))
+    }
+
+    /// Onside of any loopy game, i.e. the game where infinite play is a win for Left
+    pub fn onside_of(graph: &GameGraph, start: VertexIndex) -> Self {
+        let (graph, start) = graph.onside(start);
+        Self { graph, start }
+    }
+
+    /// Offside of any loopy game, i.e. the game where infinite play is a win for Right
+    pub fn offside_of(graph: &GameGraph, start: VertexIndex) -> Self {
+        -Self::onside_of(&graph.negative(), start)
+    }
+
+    /// `on = {on|}`
+    pub fn on() -> Self {
+        let mut graph = GameGraph::new();
+        let on = graph.add_vertex();
+        graph.add_left_edge(on, on);
+        Self { graph, start: on }
+    }
+
+    /// `off = {|off}`
+    pub fn off() -> Self {
+        -Self::on()
+    }
+
+    /// `over = {0|over}`
+    pub fn over() -> Self {
+        let mut graph = GameGraph::new();
+        let over = graph.add_vertex();
+        let zero = graph.add_vertex();
+        graph.add_left_edge(over, zero);
+        graph.add_right_edge(over, over);
+        Self { graph, start: over }
+    }
+
+    /// `under = {under|0}`
+    pub fn under() -> Self {
+        -Self::over()
+    }
+
+    /// `upon = {upon|*}`
+    pub fn upon() -> Self {
+        let mut graph = GameGraph::new();
+        let upon = graph.add_vertex();
+        let star = graph.add_vertex();
+        let zero = graph.add_vertex();
+        graph.add_left_edge(upon, upon);
+        graph.add_right_edge(upon, star);
+        graph.add_left_edge(star, zero);
+        graph.add_right_edge(star, zero);
+        Self { graph, start: upon }
+    }
+
+    /// Graph of the stopper
+    #[inline]
+    pub const fn graph(&self) -> &GameGraph {
+        &self.graph
+    }
+
+    /// Starting vertex of the stopper
+    #[inline]
+    pub const fn start(&self) -> VertexIndex {
+        self.start
+    }
+
+    /// Check if one stopper is less than or equal to another
+    pub fn leq(lhs: &Self, rhs: &Self) -> bool {
+        let survives = GameGraph::left_survives(&rhs.graph, &lhs.graph);
+        survives[rhs.start.index * lhs.graph.size() + lhs.start.index]
+    }
+
+    /// Upsum of two stoppers, i.e. onside of their sum
+    #[must_use]
+    pub fn upsum(&self, other: &Self) -> Self {
+        let (graph, start) = GameGraph::sum(&self.graph, self.start, &other.graph, other.start);
+        Self::onside_of(&graph, start)
+    }
+
+    /// Downsum of two stoppers, i.e. offside of their sum
+    #[must_use]
+    pub fn downsum(&self, other: &Self) -> Self {
+        let (graph, start) = GameGraph::sum(&self.graph, self.start, &other.graph, other.start);
+        Self::offside_of(&graph, start)
+    }
+
+    /// Convert to short game if the stopper has no cycles
+    pub fn to_canonical_form(&self) -> Option<CanonicalForm> {
+        let loopfree = self.graph.loopfree();
+        loopfree[self.start.index].then(|| self.short_game(self.start))
+    }
+
+    fn short_game(&self, vertex: VertexIndex) -> CanonicalForm {
+        CanonicalForm::new_from_moves(
+            self.graph
+                .left_edges(vertex)
+                .iter()
+                .map(|&m| self.short_game(m))
+                .collect(),
+            self.graph
+                .right_edges(vertex)
+                .iter()
+                .map(|&m| self.short_game(m))
+                .collect(),
+        )
+    }
+
+    fn find_labels(
+        &self,
+        vertex: VertexIndex,
+        path: &mut Vec<VertexIndex>,
+        ctx: &mut DisplayContext,
+    ) {
+        if ctx.loopfree[vertex.index] {
+            return;
+        }
+        path.push(vertex);
+        let moves = self.graph.left_edges(vertex).iter();
+        for &m in moves.chain(self.graph.right_edges(vertex)) {
+            if m == vertex {
+                continue;
+            }
+            if path.contains(&m) {
+                ctx.labelled[m.index] = true;
+            } else {
+                self.find_labels(m, path, ctx);
+            }
+        }
+        path.pop();
+    }
+
+    fn write_vertex(
+        &self,
+        w: &mut impl Write,
+        vertex: VertexIndex,
+        path: &mut Vec<VertexIndex>,
+        ctx: &mut DisplayContext,
+    ) -> fmt::Result {
+        if ctx.loopfree[vertex.index] {
+            return write!(w, "{}", self.short_game(vertex));
+        }
+
+        if ctx.labelled[vertex.index] {
+            let label = variable_name(ctx.labels.iter().flatten().count());
+            ctx.labels[vertex.index] = Some(label);
+        }
+
+        path.push(vertex);
+        let mut moves = String::new();
+        moves.push('{');
+        for (idx, &m) in self.graph.left_edges(vertex).iter().enumerate() {
+            if idx != 0 {
+                moves.push_str(", ");
+            }
+            self.write_option(&mut moves, m, path, ctx)?;
+        }
+        moves.push('|');
+        for (idx, &m) in self.graph.right_edges(vertex).iter().enumerate() {
+            if idx != 0 {
+                moves.push_str(", ");
+            }
+            self.write_option(&mut moves, m, path, ctx)?;
+        }
+        moves.push('}');
+        path.pop();
+
+        match &ctx.labels[vertex.index] {
+            Some(label) => write!(w, "{label}:{moves}"),
+            None => {
+                let name = NAMED_STOPPERS
+                    .iter()
+                    .find(|(form, _)| *form == moves)
+                    .map_or(moves.as_str(), |(_, name)| name);
+                write!(w, "{name}")
+            }
+        }
+    }
+
+    fn write_option(
+        &self,
+        w: &mut impl Write,
+        option: VertexIndex,
+        path: &mut Vec<VertexIndex>,
+        ctx: &mut DisplayContext,
+    ) -> fmt::Result {
+        if path.last() == Some(&option) {
+            write!(w, "pass")
+        } else if path.contains(&option) {
+            write!(w, "{}", ctx.labels[option.index].as_deref().unwrap_or("?"))
+        } else {
+            self.write_vertex(w, option, path, ctx)
+        }
+    }
+}
+
+const NAMED_STOPPERS: [(&str, &str); 5] = [
+    ("{pass|}", "on"),
+    ("{|pass}", "off"),
+    ("{0|pass}", "over"),
+    ("{pass|0}", "under"),
+    ("{pass|*}", "upon"),
+];
+
+struct DisplayContext {
+    loopfree: Vec<bool>,
+    labelled: Vec<bool>,
+    labels: Vec<Option<String>>,
+}
+
+impl From<CanonicalForm> for Stopper {
+    fn from(game: CanonicalForm) -> Self {
+        let (graph, start) = GameGraph::from_canonical_form(&game);
+        Self { graph, start }
+    }
+}
+
+impl PartialEq for Stopper {
+    fn eq(&self, other: &Self) -> bool {
+        Self::leq(self, other) && Self::leq(other, self)
+    }
+}
+
+impl PartialOrd for Stopper {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (Self::leq(self, other), Self::leq(other, self)) {
+            (true, true) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Less),
+            (false, true) => Some(Ordering::Greater),
+            (false, false) => None,
+        }
+    }
+}
+
+impl_op_ex!(-|g: &Stopper| -> Stopper {
+    Stopper {
+        graph: g.graph.negative(),
+        start: g.start,
+    }
+});
+
+impl Display for Stopper {
+    /// Print stopper using `{G^L|G^R}` notation, where `pass` refers to the enclosing position
+    /// and `a:{...}` labels a position that is referred to deeper in the game
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut ctx = DisplayContext {
+            loopfree: self.graph.loopfree(),
+            labelled: vec![false; self.graph.size()],
+            labels: vec![None; self.graph.size()],
+        };
+        self.find_labels(self.start, &mut Vec::new(), &mut ctx);
+        self.write_vertex(f, self.start, &mut Vec::new(), &mut ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    macro_rules! stopper {
+        ($str:expr) => {
+            Stopper::from(CanonicalForm::from_str($str).unwrap())
+        };
+    }
+
+    #[test]
+    fn named_stoppers() {
+        assert_eq!(Stopper::on().to_string(), "on");
+        assert_eq!(Stopper::off().to_string(), "off");
+        assert_eq!(Stopper::over().to_string(), "over");
+        assert_eq!(Stopper::under().to_string(), "under");
+        assert_eq!(Stopper::upon().to_string(), "upon");
+        assert_eq!((-Stopper::upon()).to_string(), "{*|pass}");
+        assert_eq!(stopper!("{1|*}").to_string(), "{1|*}");
+    }
+
+    #[test]
+    fn many_labels() {
+        // Every position can move back to the previous one, so all but the last are labelled.
+        // Not in the simplest form, which would be `on`.
+        let mut graph = GameGraph::new();
+        let vertices = (0..30).map(|_| graph.add_vertex()).collect::<Vec<_>>();
+        for pair in vertices.windows(2) {
+            graph.add_left_edge(pair[0], pair[1]);
+            graph.add_left_edge(pair[1], pair[0]);
+        }
+        let stopper = Stopper {
+            graph,
+            start: vertices[0],
+        }
+        .to_string();
+        assert!(stopper.starts_with("a:{b:{a, c:{b, "));
+        assert!(stopper.contains(", z:{y, x27:{z, x28:{x27, x29:{x28, {x29|}|}|}|}|}"));
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(Stopper::on() > stopper!("100"));
+        assert!(Stopper::off() < stopper!("-100"));
+        assert!(Stopper::over() > stopper!("0"));
+        assert!(Stopper::over() < stopper!("1/64"));
+        assert!(Stopper::under() < stopper!("0"));
+        assert!(Stopper::upon() > stopper!("0"));
+        assert_eq!(Stopper::upon().partial_cmp(&stopper!("*")), None);
+        assert_eq!(stopper!("{1|-1}").partial_cmp(&stopper!("0")), None);
+    }
+
+    #[test]
+    fn sums() {
+        assert_eq!(Stopper::on().upsum(&stopper!("1")).to_string(), "on");
+        assert_eq!(Stopper::on().upsum(&Stopper::off()).to_string(), "on");
+        assert_eq!(Stopper::on().downsum(&Stopper::off()).to_string(), "off");
+        assert_eq!(
+            Stopper::over().upsum(&stopper!("1")).to_string(),
+            "{1|pass}"
+        );
+        assert_eq!(
+            stopper!("1/2").upsum(&stopper!("1/2")).to_canonical_form(),
+            Some(CanonicalForm::new_integer(1))
+        );
+    }
+}
@@ -1,5 +1,7 @@
 //! Under the misere play condition the first player that cannot move wins
 
+pub mod genus;
 pub mod left_dead_end;
 pub mod p_free;
 pub mod quelhas;
+pub mod quotient;
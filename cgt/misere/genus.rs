@@ -0,0 +1,202 @@
+//! Conway's genus of impartial games
+
+use crate::{
+    numeric::nimber::Nimber,
+    short::{
+        impartial::impartial_game::ImpartialGame,
+        partizan::transposition_table::ParallelTranspositionTable,
+    },
+};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    rc::Rc,
+};
+
+/// Number of misère values computed for the genus, before the alternating tail
+const GENUS_LENGTH: usize = 12;
+
+/// Genus `g^(γ0 γ1 γ2 ...)` of an impartial game, where `g` is the normal play Grundy value
+/// and `γn` is the misère Grundy value of the game summed with `n` copies of `*2`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Genus {
+    normal: Nimber,
+    misere: Vec<Nimber>,
+}
+
+impl Genus {
+    /// Compute genus of an impartial game
+    pub fn new<G>(game: &G) -> Self
+    where
+        G: ImpartialGame,
+    {
+        let table = misere_table(game, &mut HashMap::new());
+        let mut misere = (0..=GENUS_LENGTH)
+            .map(|sums| table[sums][0])
+            .collect::<Vec<_>>();
+
+        // Eventually the values alternate, so we drop the repeating tail
+        while misere.len() > 2 && misere[misere.len() - 1] == misere[misere.len() - 3] {
+            misere.pop();
+        }
+
+        Self {
+            normal: game.nim_value(&ParallelTranspositionTable::new()),
+            misere,
+        }
+    }
+
+    /// Normal play Grundy value
+    #[inline]
+    pub const fn normal(&self) -> Nimber {
+        self.normal
+    }
+
+    /// Misère Grundy values of the game summed with increasing number of `*2`, without the
+    /// alternating tail
+    #[inline]
+    pub fn misere(&self) -> &[Nimber] {
+        &self.misere
+    }
+
+    /// Misère Grundy value of the game
+    #[inline]
+    pub fn misere_value(&self) -> Nimber {
+        self.misere[0]
+    }
+
+    /// Misère Grundy value of the game summed with `sums` copies of `*2`
+    pub fn misere_value_with(&self, sums: usize) -> Nimber {
+        if sums < self.misere.len() {
+            self.misere[sums]
+        } else {
+            let tail_start = self.misere.len() - 2;
+            self.misere[tail_start + (sums - tail_start) % 2]
+        }
+    }
+
+    /// Check if the game is tame, i.e. its genus is the same as of some nim heap
+    pub fn is_tame(&self) -> bool {
+        let g = self.normal.value();
+        let expected = match g {
+            0 => vec![1, 2, 0],
+            1 => vec![0, 3, 1],
+            _ => vec![g, g ^ 2],
+        };
+        self.misere.iter().map(Nimber::value).eq(expected)
+    }
+}
+
+/// Misère Grundy values of `game + a * *2 + b * *1` indexed with `[a][b]`, memoized in `known`
+/// for every position reached
+fn misere_table<G>(game: &G, known: &mut HashMap<G, Rc<Vec<Vec<Nimber>>>>) -> Rc<Vec<Vec<Nimber>>>
+where
+    G: ImpartialGame,
+{
+    if let Some(table) = known.get(game) {
+        return table.clone();
+    }
+
+    let moves = game
+        .moves()
+        .iter()
+        .map(|m| misere_table(m, known))
+        .collect::<Vec<_>>();
+
+    let mut table: Vec<Vec<Nimber>> = Vec::with_capacity(GENUS_LENGTH + 1);
+    for twos in 0..=GENUS_LENGTH {
+        let mut row = Vec::with_capacity(2 * GENUS_LENGTH - twos + 1);
+        for ones in 0..=(2 * GENUS_LENGTH - twos) {
+            let mut for_mex = moves.iter().map(|m| m[twos][ones]).collect::<Vec<_>>();
+            if twos > 0 {
+                for_mex.push(table[twos - 1][ones]);
+                for_mex.push(table[twos - 1][ones + 1]);
+            }
+            if ones > 0 {
+                for_mex.push(row[ones - 1]);
+            }
+
+            // Under misère play condition the terminal position is a first player win
+            row.push(if for_mex.is_empty() {
+                Nimber::new(1)
+            } else {
+                Nimber::mex(for_mex)
+            });
+        }
+        table.push(row);
+    }
+
+    let table = Rc::new(table);
+    known.insert(game.clone(), table.clone());
+    table
+}
+
+impl Display for Genus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}^", self.normal.value())?;
+        if self.misere.iter().all(|m| m.value() < 10) {
+            for m in &self.misere {
+                write!(f, "{}", m.value())?;
+            }
+            Ok(())
+        } else {
+            let misere = self.misere.iter().map(Nimber::value).collect::<Vec<_>>();
+            crate::display::parens(f, |f| crate::display::commas(f, &misere))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct Heap(u32);
+
+    impl ImpartialGame for Heap {
+        fn moves(&self) -> Vec<Self> {
+            (0..self.0).map(Heap).collect()
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Sum(Vec<u32>);
+
+    impl ImpartialGame for Sum {
+        fn moves(&self) -> Vec<Self> {
+            let mut moves = Vec::new();
+            for (idx, heap) in self.0.iter().enumerate() {
+                for smaller in 0..*heap {
+                    let mut m = self.0.clone();
+                    m[idx] = smaller;
+                    moves.push(Sum(m));
+                }
+            }
+            moves
+        }
+    }
+
+    #[test]
+    fn nim_heaps() {
+        assert_eq!(Heap(0).genus().to_string(), "0^120");
+        assert_eq!(Heap(1).genus().to_string(), "1^031");
+        assert_eq!(Heap(2).genus().to_string(), "2^20");
+        assert_eq!(Heap(3).genus().to_string(), "3^31");
+        assert!((0..6).all(|n| Heap(n).genus().is_tame()));
+    }
+
+    #[test]
+    fn sums() {
+        // * + * has the same genus as 0, but *2 + *2 does not
+        assert_eq!(Sum(vec![1, 1]).genus(), Heap(0).genus());
+        assert_eq!(Sum(vec![2, 2]).genus().to_string(), "0^02");
+        assert_eq!(Sum(vec![1, 2, 3]).genus().to_string(), "0^02");
+        assert_eq!(Sum(vec![2, 2]).genus().misere_value_with(5), Nimber::new(2));
+
+        // Positions reachable in many ways are computed once
+        let genus = Sum(vec![4, 4, 4, 5]).genus();
+        assert_eq!(genus.normal(), Nimber::new(1));
+        assert_eq!(genus.misere_value(), Nimber::new(1));
+    }
+}
@@ -0,0 +1,625 @@
+//! Misère quotients of heap games, in the style of Plambeck and Siegel
+//!
+//! Quotient is learned from outcomes of positions with bounded number of tokens and then
+//! verified for all positions, so only correct quotients are returned.
+
+use crate::{display::variable_name, short::impartial::heap_game::HeapGame};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{self, Display, Write},
+};
+
+/// Maximum number of positions examined while learning the quotient
+const MAX_POSITIONS: usize = 1 << 17;
+
+/// Misère quotient of a heap game restricted to heaps of bounded size, i.e. commutative monoid
+/// of positions modulo misère indistinguishability, with the set of P-positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MisereQuotient {
+    multiplication: Vec<Vec<usize>>,
+    p_portion: Vec<bool>,
+    heaps: Vec<usize>,
+    generators: Vec<u32>,
+    normal_forms: Vec<Vec<u32>>,
+    relations: Vec<(Vec<u32>, Vec<u32>)>,
+}
+
+impl MisereQuotient {
+    /// Compute misère quotient of positions with heaps up to `max_heap` tokens. Returns `None`
+    /// if quotient could not be found, e.g. because it is infinite.
+    pub fn new<G>(game: &G, max_heap: u32) -> Option<Self>
+    where
+        G: HeapGame,
+    {
+        let heap_moves = (0..=max_heap)
+            .map(|heap| {
+                game.heap_moves(heap)
+                    .into_iter()
+                    .map(|mut m| {
+                        m.retain(|&h| h != 0);
+                        m
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let max_heap = max_heap.max(1);
+        let mut bound = 4 * max_heap;
+        loop {
+            let universe = Universe::new(&heap_moves, bound)?;
+            if let Some(automaton) = Automaton::learn(&universe)
+                && automaton.verify(&heap_moves)
+            {
+                return Some(Self::from_automaton(&automaton.minimize()));
+            }
+            bound += 2 * max_heap;
+        }
+    }
+
+    fn from_automaton(automaton: &Automaton) -> Self {
+        let size = automaton.transitions.len();
+        let multiplication = (0..size)
+            .map(|x| {
+                (0..size)
+                    .map(|y| automaton.act_word(x, &automaton.representatives[y]))
+                    .collect()
+            })
+            .collect::<Vec<Vec<usize>>>();
+        let mut heaps = vec![0];
+        heaps.extend((0..automaton.heaps()).map(|h| automaton.transitions[0][h]));
+
+        // Heap becomes a generator if it cannot be expressed with smaller generators
+        let mut generators = Vec::new();
+        let mut generated = HashSet::from([0]);
+        for (heap, &image) in heaps.iter().enumerate().skip(1) {
+            if generated.contains(&image) {
+                continue;
+            }
+            generators.push(heap as u32);
+            let mut queue = generated.iter().copied().collect::<VecDeque<_>>();
+            while let Some(x) = queue.pop_front() {
+                for &g in &generators {
+                    let y = multiplication[x][heaps[g as usize]];
+                    if generated.insert(y) {
+                        queue.push_back(y);
+                    }
+                }
+            }
+        }
+
+        // Normal forms are the shortlex-smallest words, relations rewrite minimal other words
+        let mut normal_forms: Vec<Option<Vec<u32>>> = vec![None; size];
+        normal_forms[0] = Some(vec![0; generators.len()]);
+        let mut normal = HashMap::from([(vec![0; generators.len()], 0)]);
+        let mut relations = Vec::new();
+        let mut previous_degree = vec![vec![0; generators.len()]];
+        while !previous_degree.is_empty() {
+            let mut candidates = previous_degree
+                .iter()
+                .flat_map(|word| {
+                    (0..generators.len()).map(move |g| {
+                        let mut word = word.clone();
+                        word[g] += 1;
+                        word
+                    })
+                })
+                .collect::<Vec<_>>();
+            candidates.sort_unstable_by(|lhs, rhs| rhs.cmp(lhs));
+            candidates.dedup();
+
+            let mut current_degree = Vec::new();
+            for word in candidates {
+                let mut element = None;
+                let mut reducible = false;
+                for (g, &exponent) in word.iter().enumerate() {
+                    if exponent == 0 {
+                        continue;
+                    }
+                    let mut smaller = word.clone();
+                    smaller[g] -= 1;
+                    match normal.get(&smaller) {
+                        Some(&x) => {
+                            element = Some(multiplication[x][heaps[generators[g] as usize]]);
+                        }
+                        None => reducible = true,
+                    }
+                }
+                let Some(element) = element.filter(|_| !reducible) else {
+                    continue;
+                };
+                match &normal_forms[element] {
+                    Some(normal_form) => relations.push((word, normal_form.clone())),
+                    None => {
+                        normal_forms[element] = Some(word.clone());
+                        normal.insert(word.clone(), element);
+                        current_degree.push(word);
+                    }
+                }
+            }
+            previous_degree = current_degree;
+        }
+
+        Self {
+            multiplication,
+            p_portion: automaton.p_portion.clone(),
+            heaps,
+            generators,
+            normal_forms: normal_forms.into_iter().map(Option::unwrap).collect(),
+            relations,
+        }
+    }
+
+    /// Number of elements in the quotient
+    #[inline]
+    pub const fn size(&self) -> usize {
+        self.multiplication.len()
+    }
+
+    /// Identity element of the quotient, i.e. the image of the empty position
+    #[inline]
+    pub const fn identity(&self) -> usize {
+        0
+    }
+
+    /// Product of two elements
+    #[inline]
+    pub fn multiply(&self, lhs: usize, rhs: usize) -> usize {
+        self.multiplication[lhs][rhs]
+    }
+
+    /// Image of a single heap in the quotient
+    #[inline]
+    pub fn heap_image(&self, heap: u32) -> usize {
+        self.heaps[heap as usize]
+    }
+
+    /// Image of a sum of heaps in the quotient
+    pub fn image(&self, heaps: &[u32]) -> usize {
+        heaps.iter().fold(self.identity(), |acc, &heap| {
+            self.multiply(acc, self.heap_image(heap))
+        })
+    }
+
+    /// Check if element is in the P-portion
+    #[inline]
+    pub fn is_p(&self, element: usize) -> bool {
+        self.p_portion[element]
+    }
+
+    /// Check if a sum of heaps is a misère P-position
+    pub fn is_p_position(&self, heaps: &[u32]) -> bool {
+        self.is_p(self.image(heaps))
+    }
+
+    /// Elements of the P-portion
+    pub fn p_portion(&self) -> Vec<usize> {
+        (0..self.size()).filter(|&x| self.is_p(x)).collect()
+    }
+
+    /// Heap sizes that generate the quotient
+    #[inline]
+    pub fn generators(&self) -> &[u32] {
+        &self.generators
+    }
+
+    /// Element written as a word in generators
+    pub fn word(&self, element: usize) -> String {
+        format_word(&self.normal_forms[element])
+    }
+}
+
+fn format_word(word: &[u32]) -> String {
+    let mut result = String::new();
+    for (g, &exponent) in word.iter().enumerate() {
+        if exponent == 0 {
+            continue;
+        }
+        result.push_str(&variable_name(g));
+        if exponent > 1 {
+            let _ = write!(result, "^{exponent}");
+        }
+    }
+    if result.is_empty() {
+        result.push('1');
+    }
+    result
+}
+
+impl Display for MisereQuotient {
+    /// Print monoid presentation `<a, b | a^2 = 1, b^3 = b>` followed by the P-portion
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let generators = (0..self.generators.len())
+            .map(variable_name)
+            .collect::<Vec<_>>();
+        let relations = self
+            .relations
+            .iter()
+            .map(|(lhs, rhs)| format!("{} = {}", format_word(lhs), format_word(rhs)))
+            .collect::<Vec<_>>();
+        let p_portion = self
+            .p_portion()
+            .into_iter()
+            .map(|x| self.word(x))
+            .collect::<Vec<_>>();
+
+        write!(f, "<")?;
+        crate::display::commas(f, &generators)?;
+        if !relations.is_empty() {
+            write!(f, " | ")?;
+            crate::display::commas(f, &relations)?;
+        }
+        write!(f, ">, P = ")?;
+        crate::display::braces(f, |f| crate::display::commas(f, &p_portion))
+    }
+}
+
+/// Outcomes of all positions with bounded number of tokens
+struct Universe {
+    /// Positions as heap counts where `counts[h]` is the number of heaps with `h + 1` tokens
+    positions: Vec<Vec<u32>>,
+    indices: HashMap<Vec<u32>, usize>,
+    is_p: Vec<bool>,
+    bound: u32,
+}
+
+impl Universe {
+    fn new(heap_moves: &[Vec<Vec<u32>>], bound: u32) -> Option<Self> {
+        let heaps = heap_moves.len() - 1;
+        let mut positions = vec![vec![0; heaps]];
+        let mut totals = vec![0];
+        // Positions are generated in the order of increasing number of tokens
+        for total in 1..=bound {
+            let mut current = Vec::new();
+            for (position, &position_total) in positions.iter().zip(&totals) {
+                let largest = position.iter().rposition(|&c| c > 0).map_or(0, |h| h + 1);
+                for heap in largest.max(1)..=heaps {
+                    if position_total + heap as u32 == total {
+                        let mut position = position.clone();
+                        position[heap - 1] += 1;
+                        current.push(position);
+                    }
+                }
+            }
+            totals.extend(current.iter().map(|_| total));
+            positions.extend(current);
+            if positions.len() > MAX_POSITIONS {
+                return None;
+            }
+        }
+
+        let indices = positions
+            .iter()
+            .enumerate()
+            .map(|(idx, position)| (position.clone(), idx))
+            .collect::<HashMap<_, _>>();
+
+        let mut is_p = Vec::with_capacity(positions.len());
+        for position in &positions {
+            let mut has_move = false;
+            let mut has_p_move = false;
+            for (h, &count) in position.iter().enumerate() {
+                if count == 0 {
+                    continue;
+                }
+                for heap_move in &heap_moves[h + 1] {
+                    let mut next = position.clone();
+                    next[h] -= 1;
+                    for &heap in heap_move {
+                        next[heap as usize - 1] += 1;
+                    }
+                    has_move = true;
+                    has_p_move |= is_p[indices[&next]];
+                }
+            }
+            // Player who cannot move wins
+            is_p.push(has_move && !has_p_move);
+        }
+
+        Some(Self {
+            positions,
+            indices,
+            is_p,
+            bound,
+        })
+    }
+
+    fn total(position: &[u32]) -> u32 {
+        position
+            .iter()
+            .enumerate()
+            .map(|(h, &count)| (h as u32 + 1) * count)
+            .sum()
+    }
+}
+
+/// Automaton recognizing P-positions, where reading a heap moves to the image of position with
+/// that heap added
+#[derive(Debug)]
+struct Automaton {
+    transitions: Vec<Vec<usize>>,
+    p_portion: Vec<bool>,
+    representatives: Vec<Vec<u32>>,
+}
+
+impl Automaton {
+    fn heaps(&self) -> usize {
+        self.transitions[0].len()
+    }
+
+    fn act_word(&self, state: usize, position: &[u32]) -> usize {
+        let mut state = state;
+        for (h, &count) in position.iter().enumerate() {
+            for _ in 0..count {
+                state = self.transitions[state][h];
+            }
+        }
+        state
+    }
+
+    fn act_heaps(&self, state: usize, heaps: &[u32]) -> usize {
+        heaps.iter().fold(state, |state, &heap| {
+            self.transitions[state][heap as usize - 1]
+        })
+    }
+
+    /// Learn automaton by distinguishing positions with at most half of the tokens
+    fn learn(universe: &Universe) -> Option<Self> {
+        let half = universe.bound / 2;
+        let small = universe
+            .positions
+            .iter()
+            .filter(|p| Universe::total(p) <= half)
+            .collect::<Vec<_>>();
+
+        let mut classes = HashMap::new();
+        let mut class_of = Vec::with_capacity(small.len());
+        let mut representatives = Vec::new();
+        for x in &small {
+            let signature = small
+                .iter()
+                .map(|z| {
+                    let sum = x
+                        .iter()
+                        .zip(z.iter())
+                        .map(|(a, b)| a + b)
+                        .collect::<Vec<_>>();
+                    universe.is_p[universe.indices[&sum]]
+                })
+                .collect::<Vec<_>>();
+            let class = *classes.entry(signature).or_insert_with(|| {
+                representatives.push((*x).clone());
+                representatives.len() - 1
+            });
+            class_of.push(class);
+        }
+
+        let small_index = small
+            .iter()
+            .enumerate()
+            .map(|(idx, p)| ((*p).clone(), idx))
+            .collect::<HashMap<_, _>>();
+        let heaps = universe.positions[0].len();
+        let mut transitions = vec![vec![None; heaps]; representatives.len()];
+        for (idx, x) in small.iter().enumerate() {
+            for (h, transition) in transitions[class_of[idx]].iter_mut().enumerate() {
+                let mut next = (*x).clone();
+                next[h] += 1;
+                let Some(&next) = small_index.get(&next) else {
+                    continue;
+                };
+                // Adding a heap must respect the classes
+                match transition {
+                    Some(class) if *class != class_of[next] => return None,
+                    _ => *transition = Some(class_of[next]),
+                }
+            }
+        }
+
+        let p_portion = representatives
+            .iter()
+            .map(|r| universe.is_p[universe.indices[r]])
+            .collect();
+        let transitions = transitions
+            .into_iter()
+            .map(|row| row.into_iter().collect::<Option<Vec<_>>>())
+            .collect::<Option<Vec<_>>>()?;
+        let automaton = Self {
+            transitions,
+            p_portion,
+            representatives,
+        };
+
+        // Order of adding heaps must not matter
+        for x in 0..automaton.transitions.len() {
+            for i in 0..heaps {
+                for j in 0..i {
+                    let ij = automaton.transitions[automaton.transitions[x][i]][j];
+                    let ji = automaton.transitions[automaton.transitions[x][j]][i];
+                    if ij != ji {
+                        return None;
+                    }
+                }
+            }
+        }
+        Some(automaton)
+    }
+
+    /// Check that for every position the automaton agrees with the misère outcome. Positions
+    /// are grouped by their image and images of positions with one heap removed.
+    fn verify(&self, heap_moves: &[Vec<Vec<u32>>]) -> bool {
+        let heaps = self.heaps();
+        let start = (0, vec![None; heaps]);
+        if self.p_portion[0] {
+            return false;
+        }
+
+        let mut seen = HashSet::from([start.clone()]);
+        let mut queue = VecDeque::from([start]);
+        while let Some((state, without)) = queue.pop_front() {
+            for heap in 0..heaps {
+                let next_state = self.transitions[state][heap];
+                let next_without = without
+                    .iter()
+                    .enumerate()
+                    .map(|(h, w): (usize, &Option<usize>)| {
+                        if h == heap {
+                            Some(state)
+                        } else {
+                            w.map(|w| self.transitions[w][heap])
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                let has_p_move = next_without.iter().enumerate().any(|(h, w)| {
+                    w.is_some_and(|w| {
+                        heap_moves[h + 1]
+                            .iter()
+                            .any(|m| self.p_portion[self.act_heaps(w, m)])
+                    })
+                });
+                let has_move = next_without
+                    .iter()
+                    .enumerate()
+                    .any(|(h, w)| w.is_some() && !heap_moves[h + 1].is_empty());
+                if self.p_portion[next_state] != (has_move && !has_p_move) {
+                    return false;
+                }
+
+                let next = (next_state, next_without);
+                if seen.insert(next.clone()) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        true
+    }
+
+    /// Merge states that cannot be distinguished by any position
+    fn minimize(&self) -> Self {
+        let size = self.transitions.len();
+        let mut block = self
+            .p_portion
+            .iter()
+            .map(|&p| usize::from(p))
+            .collect::<Vec<_>>();
+        loop {
+            let mut signatures = HashMap::new();
+            let mut next_block = Vec::with_capacity(size);
+            for state in 0..size {
+                let signature = (
+                    block[state],
+                    self.transitions[state]
+                        .iter()
+                        .map(|&next| block[next])
+                        .collect::<Vec<_>>(),
+                );
+                let len = signatures.len();
+                next_block.push(*signatures.entry(signature).or_insert(len));
+            }
+            let done = signatures.len() == block.iter().collect::<HashSet<_>>().len();
+            block = next_block;
+            if done {
+                break;
+            }
+        }
+
+        // Keep identity as the first element and renumber in order of first occurrence
+        let mut renumber = HashMap::new();
+        let mut representatives = Vec::new();
+        for (state, block) in block.iter().enumerate() {
+            renumber.entry(*block).or_insert_with(|| {
+                representatives.push(state);
+                representatives.len() - 1
+            });
+        }
+        Self {
+            transitions: representatives
+                .iter()
+                .map(|&state| {
+                    self.transitions[state]
+                        .iter()
+                        .map(|next| renumber[&block[*next]])
+                        .collect()
+                })
+                .collect(),
+            p_portion: representatives
+                .iter()
+                .map(|&state| self.p_portion[state])
+                .collect(),
+            representatives: representatives
+                .iter()
+                .map(|&state| self.representatives[state].clone())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::impartial::games::subtraction::Sub;
+
+    fn is_p_position(game: &Sub, heaps: &[u32], cache: &mut HashMap<Vec<u32>, bool>) -> bool {
+        let mut heaps = heaps
+            .iter()
+            .copied()
+            .filter(|&h| h != 0)
+            .collect::<Vec<_>>();
+        heaps.sort_unstable();
+        if let Some(&is_p) = cache.get(&heaps) {
+            return is_p;
+        }
+        let mut has_move = false;
+        let mut has_p_move = false;
+        for idx in 0..heaps.len() {
+            for m in game.heap_moves(heaps[idx]) {
+                let mut next = heaps.clone();
+                next.remove(idx);
+                next.extend(m);
+                has_move = true;
+                has_p_move |= is_p_position(game, &next, cache);
+            }
+        }
+        let is_p = has_move && !has_p_move;
+        cache.insert(heaps, is_p);
+        is_p
+    }
+
+    #[test]
+    fn nim_quotients() {
+        let quotient = MisereQuotient::new(&Sub::new(vec![1]), 5).unwrap();
+        assert_eq!(quotient.to_string(), "<a | a^2 = 1>, P = {a}");
+
+        let quotient = MisereQuotient::new(&Sub::new(vec![1, 2]), 2).unwrap();
+        assert_eq!(
+            quotient.to_string(),
+            "<a, b | a^2 = 1, b^3 = b>, P = {a, b^2}"
+        );
+    }
+
+    #[test]
+    fn agrees_with_outcomes() {
+        for (game, max_heap) in [
+            (Sub::new(vec![1, 2]), 6),
+            (Sub::new(vec![2, 3]), 6),
+            (Sub::new(vec![1, 3, 4]), 5),
+        ] {
+            let quotient = MisereQuotient::new(&game, max_heap).unwrap();
+            let mut cache = HashMap::new();
+            for a in 0..=max_heap {
+                for b in 0..=a {
+                    for c in 0..=b {
+                        for d in 0..=c {
+                            let heaps = [a, b, c, d];
+                            assert_eq!(
+                                quotient.is_p_position(&heaps),
+                                is_p_position(&game, &heaps, &mut cache)
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
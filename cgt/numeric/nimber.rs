@@ -1,11 +1,16 @@
 //! Nimber is a number that represents a Nim heap of a given size.
 
+use crate::total::TotalWrappable;
 use auto_ops::impl_op_ex;
-use std::fmt::Display;
+use std::{cmp::Ordering, fmt::Display, hash::Hash, iter::Sum};
 
 /// Number that represents a Nim heap of given size.
 ///
-/// Addition is overloaded to Nim sum.
+/// Addition is overloaded to Nim sum and multiplication to Nim product, making nimbers below
+/// `2^32` a field.
+///
+/// # Panics
+/// - Division by zero, use [`Nimber::checked_div`] to handle it
 #[repr(transparent)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -36,6 +41,138 @@ impl Nimber {
         }
         Self(current)
     }
+
+    /// Multiplicative inverse, `None` for zero
+    pub fn inverse(&self) -> Option<Self> {
+        nim_inverse(u128::from(self.0)).map(|n| Self(n as u32))
+    }
+
+    /// Nim division, `None` if `rhs` is zero
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        rhs.inverse().map(|inverse| self * inverse)
+    }
+
+    /// Unique nimber whose square is equal to the given one
+    #[must_use]
+    pub fn sqrt(&self) -> Self {
+        Self(nim_sqrt(u128::from(self.0)) as u32)
+    }
+
+    /// Raise nimber to a power using Nim product
+    #[must_use]
+    pub fn pow(&self, exponent: u64) -> Self {
+        Self(nim_pow(u128::from(self.0), exponent) as u32)
+    }
+}
+
+/// Nimber with values up to `2^128`, using the same field operations as [`Nimber`]
+///
+/// # Panics
+/// - Division by zero, use [`Nimber128::checked_div`] to handle it
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Nimber128(u128);
+
+impl Nimber128 {
+    /// Construct new nimber
+    pub const fn new(value: u128) -> Self {
+        Self(value)
+    }
+
+    /// Get the underlying nimber value
+    pub const fn value(&self) -> u128 {
+        self.0
+    }
+
+    /// Multiplicative inverse, `None` for zero
+    pub fn inverse(&self) -> Option<Self> {
+        nim_inverse(self.0).map(Self)
+    }
+
+    /// Nim division, `None` if `rhs` is zero
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        rhs.inverse().map(|inverse| self * inverse)
+    }
+
+    /// Unique nimber whose square is equal to the given one
+    #[must_use]
+    pub fn sqrt(&self) -> Self {
+        Self(nim_sqrt(self.0))
+    }
+
+    /// Raise nimber to a power using Nim product
+    #[must_use]
+    pub fn pow(&self, exponent: u64) -> Self {
+        Self(nim_pow(self.0, exponent))
+    }
+}
+
+/// Number of bits of the smallest Fermat 2-power field containing the value
+fn field_bits(value: u128) -> u32 {
+    (u128::BITS - value.leading_zeros())
+        .max(1)
+        .next_power_of_two()
+}
+
+/// Nim product in the field with `2^bits` elements. Value `x = 2^(bits/2)` satisfies
+/// `x * x = x + x/2`, so products split into three products in the half-sized field.
+fn nim_product_in(lhs: u128, rhs: u128, bits: u32) -> u128 {
+    if bits == 1 {
+        return lhs & rhs;
+    }
+    let half = bits / 2;
+    let mask = (1 << half) - 1;
+    let (lhs_high, lhs_low) = (lhs >> half, lhs & mask);
+    let (rhs_high, rhs_low) = (rhs >> half, rhs & mask);
+
+    let low = nim_product_in(lhs_low, rhs_low, half);
+    let high = nim_product_in(lhs_high, rhs_high, half);
+    let mixed = nim_product_in(lhs_low ^ lhs_high, rhs_low ^ rhs_high, half);
+    let high_half = nim_product_in(high, 1 << (half - 1), half);
+
+    ((mixed ^ low) << half) | (low ^ high_half)
+}
+
+fn nim_product(lhs: u128, rhs: u128) -> u128 {
+    nim_product_in(lhs, rhs, field_bits(lhs.max(rhs)))
+}
+
+fn nim_pow(base: u128, mut exponent: u64) -> u128 {
+    let bits = field_bits(base);
+    let mut base = base;
+    let mut result = 1;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = nim_product_in(result, base, bits);
+        }
+        base = nim_product_in(base, base, bits);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// In the field with `2^bits` elements squaring is an automorphism of order `bits`, so square
+/// root is squaring `bits - 1` times.
+fn nim_sqrt(value: u128) -> u128 {
+    let bits = field_bits(value);
+    (1..bits).fold(value, |acc, _| nim_product_in(acc, acc, bits))
+}
+
+/// In the field with `2^bits` elements `a^(2^bits - 1) = 1`, so inverse is `a^(2^bits - 2)`
+/// computed as product of `a^(2^i)` for `1 <= i < bits`.
+fn nim_inverse(value: u128) -> Option<u128> {
+    if value == 0 {
+        return None;
+    }
+    let bits = field_bits(value);
+    let mut square = value;
+    let mut result = 1;
+    for _ in 1..bits {
+        square = nim_product_in(square, square, bits);
+        result = nim_product_in(result, square, bits);
+    }
+    Some(result)
 }
 
 impl From<u32> for Nimber {
@@ -44,6 +181,18 @@ impl From<u32> for Nimber {
     }
 }
 
+impl TotalWrappable for Nimber {
+    #[inline(always)]
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+
+    #[inline(always)]
+    fn total_hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.hash(state);
+    }
+}
+
 // xor is correct, that's how nimbers addition works
 impl_op_ex!(+|lhs: &Nimber, rhs: &Nimber| -> Nimber { Nimber(lhs.0 ^ rhs.0) });
 impl_op_ex!(+=|lhs: &mut Nimber, rhs: &Nimber| { lhs.0 ^= rhs.0 });
@@ -55,6 +204,41 @@ impl_op_ex!(-=|lhs: &mut Nimber, rhs: &Nimber| { lhs.0 ^= rhs.0 });
 // Nimber is its own negative
 impl_op_ex!(-|lhs: &Nimber| -> Nimber { *lhs });
 
+impl_op_ex!(*|lhs: &Nimber, rhs: &Nimber| -> Nimber {
+    Nimber(nim_product(u128::from(lhs.0), u128::from(rhs.0)) as u32)
+});
+impl_op_ex!(*=|lhs: &mut Nimber, rhs: &Nimber| { *lhs = *lhs * rhs });
+impl_op_ex!(/|lhs: &Nimber, rhs: &Nimber| -> Nimber {
+    lhs * rhs.inverse().expect("attempt to divide by zero")
+});
+impl_op_ex!(/=|lhs: &mut Nimber, rhs: &Nimber| { *lhs = *lhs / rhs });
+
+impl Sum for Nimber {
+    fn sum<I: Iterator<Item = Nimber>>(iter: I) -> Nimber {
+        iter.fold(Nimber(0), |acc, v| acc + v)
+    }
+}
+
+impl From<Nimber> for Nimber128 {
+    fn from(value: Nimber) -> Self {
+        Self(u128::from(value.0))
+    }
+}
+
+impl_op_ex!(+|lhs: &Nimber128, rhs: &Nimber128| -> Nimber128 { Nimber128(lhs.0 ^ rhs.0) });
+impl_op_ex!(+=|lhs: &mut Nimber128, rhs: &Nimber128| { lhs.0 ^= rhs.0 });
+impl_op_ex!(-|lhs: &Nimber128, rhs: &Nimber128| -> Nimber128 { Nimber128(lhs.0 ^ rhs.0) });
+impl_op_ex!(-=|lhs: &mut Nimber128, rhs: &Nimber128| { lhs.0 ^= rhs.0 });
+impl_op_ex!(-|lhs: &Nimber128| -> Nimber128 { *lhs });
+impl_op_ex!(*|lhs: &Nimber128, rhs: &Nimber128| -> Nimber128 {
+    Nimber128(nim_product(lhs.0, rhs.0))
+});
+impl_op_ex!(*=|lhs: &mut Nimber128, rhs: &Nimber128| { *lhs = *lhs * rhs });
+impl_op_ex!(/|lhs: &Nimber128, rhs: &Nimber128| -> Nimber128 {
+    lhs * rhs.inverse().expect("attempt to divide by zero")
+});
+impl_op_ex!(/=|lhs: &mut Nimber128, rhs: &Nimber128| { *lhs = *lhs / rhs });
+
 impl Display for Nimber {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.0 {
@@ -65,6 +249,16 @@ impl Display for Nimber {
     }
 }
 
+impl Display for Nimber128 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            0 => write!(f, "0"),
+            1 => write!(f, "*"),
+            n => write!(f, "*{}", n),
+        }
+    }
+}
+
 #[test]
 fn mex_works() {
     assert_eq!(
@@ -84,3 +278,66 @@ fn mex_works() {
 
     assert_eq!(Nimber(0), Nimber::mex(vec![]));
 }
+
+#[test]
+fn product_works() {
+    // Nim product defined directly as mex of a'b + ab' + a'b'
+    let mut table = [[0u32; 16]; 16];
+    for a in 0..16 {
+        for b in 0..16 {
+            let mut for_mex = Vec::new();
+            for a_prime in 0..a {
+                for b_prime in 0..b {
+                    for_mex.push(Nimber(
+                        table[a_prime][b] ^ table[a][b_prime] ^ table[a_prime][b_prime],
+                    ));
+                }
+            }
+            table[a][b] = Nimber::mex(for_mex).0;
+            assert_eq!(Nimber(a as u32) * Nimber(b as u32), Nimber(table[a][b]));
+        }
+    }
+
+    assert_eq!(Nimber(16) * Nimber(16), Nimber(24));
+    assert_eq!(Nimber(1 << 16) * Nimber(1 << 16), Nimber(3 << 15));
+    assert_eq!(
+        Nimber128::new(1 << 64) * Nimber128::new(1 << 64),
+        Nimber128::new(3 << 63)
+    );
+}
+
+#[test]
+fn field_operations_work() {
+    let values = (0..200)
+        .chain([255, 256, 1 << 20, 123_456_789, u32::MAX])
+        .map(Nimber)
+        .collect::<Vec<_>>();
+    for &a in &values {
+        assert_eq!(a.sqrt() * a.sqrt(), a);
+        assert_eq!(a.pow(3), a * a * a);
+        assert_eq!(Nimber128::from(a).sqrt(), Nimber128::from(a.sqrt()));
+        if a != Nimber(0) {
+            assert_eq!(a * a.inverse().unwrap(), Nimber(1));
+            assert_eq!((a * Nimber(7)) / a, Nimber(7));
+            assert_eq!((a * Nimber(7)).checked_div(a), Some(Nimber(7)));
+        }
+        for &b in &values[..20] {
+            assert_eq!(
+                Nimber128::from(a) * Nimber128::from(b),
+                Nimber128::from(a * b)
+            );
+        }
+    }
+    assert_eq!(Nimber(0).inverse(), None);
+    assert_eq!(Nimber(5).checked_div(Nimber(0)), None);
+    assert_eq!(Nimber128::new(5).checked_div(Nimber128::new(0)), None);
+
+    let large = Nimber128::new(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210);
+    assert_eq!(large * large.inverse().unwrap(), Nimber128::new(1));
+    assert_eq!(large.checked_div(large), Some(Nimber128::new(1)));
+    assert_eq!(large.sqrt() * large.sqrt(), large);
+    assert_eq!(large.pow(5), large.pow(2) * large.pow(3));
+
+    // Nonzero nimbers below 256 form multiplicative group of order 255
+    assert!((1..256).all(|a| Nimber(a).pow(255) == Nimber(1)));
+}
@@ -1,4 +1,6 @@
 //! Short impartial games
 
 pub mod games;
+pub mod grundy_sequence;
+pub mod heap_game;
 pub mod impartial_game;
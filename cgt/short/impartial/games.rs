@@ -1,5 +1,9 @@
 //! Short impartial games
 
+pub mod arc_kayles;
+pub mod geography;
+pub mod node_kayles;
+pub mod octal;
 pub mod pseudo_quicksort;
 pub mod quicksort;
 pub mod subtraction;
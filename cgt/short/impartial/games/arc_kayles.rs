@@ -0,0 +1,111 @@
+//! Arc-Kayles is played on undirected graph. In each turn player picks an edge and removes both
+//! of its endpoints, together with all edges incident to them.
+//!
+//! Arc-Kayles played on a path of `n` vertices is Dawson's Kayles (`0.07`) played on a heap of
+//! size `n`.
+
+use crate::{
+    graph::{Graph, VertexIndex, adjacency_matrix::undirected::UndirectedGraph, canonical},
+    short::impartial::impartial_game::ImpartialGame,
+};
+
+/// See [`arc_kayles`](self) header
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArcKayles {
+    graph: UndirectedGraph<()>,
+}
+
+impl ArcKayles {
+    /// Create new Arc-Kayles position from a graph
+    #[inline]
+    pub const fn new(graph: UndirectedGraph<()>) -> Self {
+        Self { graph }
+    }
+
+    /// Get underlying graph
+    #[inline]
+    pub const fn graph(&self) -> &UndirectedGraph<()> {
+        &self.graph
+    }
+
+    /// Get position after removing both endpoints of the edge. Note that it does not check
+    /// if the edge exists
+    #[must_use]
+    pub fn remove_edge(&self, u: VertexIndex, v: VertexIndex) -> Self {
+        let remaining = self
+            .graph
+            .vertex_indices()
+            .filter(|&w| w != u && w != v)
+            .collect::<Vec<_>>();
+        Self::new(self.graph.induced_subgraph(&remaining))
+    }
+}
+
+impl ImpartialGame for ArcKayles {
+    fn moves(&self) -> Vec<Self> {
+        let mut moves = Vec::new();
+        for v in self.graph.vertex_indices() {
+            for u in self.graph.adjacent_to(v) {
+                if v < u {
+                    moves.push(self.remove_edge(v, u));
+                }
+            }
+        }
+        moves
+    }
+
+    /// Connected components in canonical labeling, so isomorphic components share the nim value
+    /// in the transposition table. Isolated vertices are dropped, as no edge can be picked there.
+    fn decompositions(&self) -> Vec<Self> {
+        canonical::canonical_components(&self.graph)
+            .into_iter()
+            .filter(|component| component.size() > 1)
+            .map(Self::new)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::transposition_table::ParallelTranspositionTable;
+    use crate::{numeric::nimber::Nimber, short::impartial::games::octal::Octal};
+    use std::str::FromStr;
+
+    fn path(n: usize) -> UndirectedGraph<()> {
+        let edges = (1..n)
+            .map(|index| (VertexIndex { index: index - 1 }, VertexIndex { index }))
+            .collect::<Vec<_>>();
+        UndirectedGraph::from_edges(&edges, &vec![(); n])
+    }
+
+    #[test]
+    fn dawsons_kayles_on_paths() {
+        let transposition_table = ParallelTranspositionTable::new();
+        let values = Octal::from_str("0.07").unwrap().grundy_values(20);
+        for (n, value) in values.into_iter().enumerate() {
+            assert_eq!(
+                ArcKayles::new(path(n)).nim_value(&transposition_table),
+                value,
+                "P{n}"
+            );
+        }
+    }
+
+    #[test]
+    fn stars() {
+        let transposition_table = ParallelTranspositionTable::new();
+        // Every move in a star removes the center, leaving only isolated vertices
+        for leaves in 1..8 {
+            let edges = (1..=leaves)
+                .map(|index| (VertexIndex { index: 0 }, VertexIndex { index }))
+                .collect::<Vec<_>>();
+            let star = UndirectedGraph::from_edges(&edges, &vec![(); leaves + 1]);
+            assert_eq!(
+                ArcKayles::new(star).nim_value(&transposition_table),
+                Nimber::new(1)
+            );
+        }
+    }
+}
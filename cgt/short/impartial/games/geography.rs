@@ -0,0 +1,147 @@
+//! Undirected Vertex Geography is played on undirected graph with a token placed on one of the
+//! vertices. In each turn player moves the token to an adjacent vertex and the vertex the token
+//! was moved from is removed from the graph.
+
+use crate::{
+    graph::{Graph, VertexIndex, adjacency_matrix::undirected::UndirectedGraph, canonical},
+    short::impartial::impartial_game::ImpartialGame,
+};
+
+/// See [geography](self) header
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VertexGeography {
+    graph: UndirectedGraph<()>,
+    token: VertexIndex,
+}
+
+impl VertexGeography {
+    /// Create new Undirected Vertex Geography position with token on a given vertex. Returns
+    /// `None` if the vertex is not in the graph
+    #[inline]
+    pub fn new(graph: UndirectedGraph<()>, token: VertexIndex) -> Option<Self> {
+        (token.index < graph.size()).then_some(Self { graph, token })
+    }
+
+    /// Get underlying graph
+    #[inline]
+    pub const fn graph(&self) -> &UndirectedGraph<()> {
+        &self.graph
+    }
+
+    /// Get vertex with the token
+    #[inline]
+    pub const fn token(&self) -> VertexIndex {
+        self.token
+    }
+
+    /// Get position after moving the token to `vertex`. Note that it does not check if the move
+    /// is legal
+    #[must_use]
+    pub fn move_token(&self, vertex: VertexIndex) -> Self {
+        let remaining = self
+            .graph
+            .vertex_indices()
+            .filter(|&v| v != self.token)
+            .collect::<Vec<_>>();
+        let token = VertexIndex {
+            index: remaining.iter().position(|&v| v == vertex).unwrap(),
+        };
+        Self {
+            graph: self.graph.induced_subgraph(&remaining),
+            token,
+        }
+    }
+}
+
+impl ImpartialGame for VertexGeography {
+    fn moves(&self) -> Vec<Self> {
+        self.graph
+            .adjacent_to(self.token)
+            .filter(|&v| v != self.token)
+            .map(|v| self.move_token(v))
+            .collect()
+    }
+
+    /// Component of the token in canonical labeling, with the token on the first vertex. Other
+    /// components can never be reached.
+    fn decompositions(&self) -> Vec<Self> {
+        let component = self
+            .graph
+            .connected_components()
+            .into_iter()
+            .find(|component| component.contains(&self.token))
+            .unwrap();
+        let graph = self.graph.induced_subgraph(&component);
+        let token = VertexIndex {
+            index: component.iter().position(|&v| v == self.token).unwrap(),
+        };
+
+        // Token is the only vertex with the smallest key, so it's first in the canonical order
+        let order = canonical::canonical_order_by_key(&graph, |v| v != token);
+        vec![Self {
+            graph: graph.induced_subgraph(&order),
+            token: VertexIndex { index: 0 },
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numeric::nimber::Nimber;
+    use crate::short::partizan::transposition_table::ParallelTranspositionTable;
+
+    fn graph(size: usize, edges: &[(usize, usize)]) -> UndirectedGraph<()> {
+        UndirectedGraph::from_edges(
+            &edges
+                .iter()
+                .map(|&(u, v)| (VertexIndex { index: u }, VertexIndex { index: v }))
+                .collect::<Vec<_>>(),
+            &vec![(); size],
+        )
+    }
+
+    #[test]
+    fn paths() {
+        let transposition_table = ParallelTranspositionTable::new();
+        for n in 1..10 {
+            let edges = (1..n).map(|v| (v - 1, v)).collect::<Vec<_>>();
+
+            // Every move is forced when starting from the end of the path
+            let position =
+                VertexGeography::new(graph(n, &edges), VertexIndex { index: 0 }).unwrap();
+            assert_eq!(
+                position.nim_value(&transposition_table),
+                Nimber::new((n as u32 - 1) % 2)
+            );
+        }
+    }
+
+    #[test]
+    fn nim_values() {
+        let transposition_table = ParallelTranspositionTable::new();
+        let star = graph(4, &[(0, 1), (0, 2), (0, 3)]);
+        let center = VertexGeography::new(star.clone(), VertexIndex { index: 0 }).unwrap();
+        assert_eq!(center.nim_value(&transposition_table), Nimber::new(1));
+        let leaf = VertexGeography::new(star, VertexIndex { index: 1 }).unwrap();
+        assert_eq!(leaf.nim_value(&transposition_table), Nimber::new(0));
+
+        // Token can move to the end of a path (*1) or to a vertex next to a center of a star (0)
+        let spider = graph(7, &[(0, 1), (1, 2), (0, 3), (3, 4), (4, 5), (4, 6)]);
+        let position = VertexGeography::new(spider, VertexIndex { index: 0 }).unwrap();
+        assert_eq!(position.nim_value(&transposition_table), Nimber::new(2));
+
+        assert!(VertexGeography::new(graph(2, &[(0, 1)]), VertexIndex { index: 2 }).is_none());
+    }
+
+    #[test]
+    fn unreachable_components() {
+        let position =
+            VertexGeography::new(graph(5, &[(3, 4), (0, 1)]), VertexIndex { index: 4 }).unwrap();
+        assert_eq!(
+            position.decompositions(),
+            vec![VertexGeography::new(graph(2, &[(0, 1)]), VertexIndex { index: 0 }).unwrap()]
+        );
+    }
+}
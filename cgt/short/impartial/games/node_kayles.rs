@@ -0,0 +1,124 @@
+//! Node-Kayles is played on undirected graph. In each turn player picks a vertex and removes it
+//! together with all adjacent vertices.
+//!
+//! Node-Kayles played on a path of `n` vertices is Dawson's Chess (`0.137`) played on a heap of
+//! size `n`, i.e. Dawson's Kayles (`0.07`) on a heap of size `n + 1`.
+
+use crate::{
+    graph::{Graph, VertexIndex, adjacency_matrix::undirected::UndirectedGraph, canonical},
+    short::impartial::impartial_game::ImpartialGame,
+};
+
+/// See [`node_kayles`](self) header
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeKayles {
+    graph: UndirectedGraph<()>,
+}
+
+impl NodeKayles {
+    /// Create new Node-Kayles position from a graph
+    #[inline]
+    pub const fn new(graph: UndirectedGraph<()>) -> Self {
+        Self { graph }
+    }
+
+    /// Get underlying graph
+    #[inline]
+    pub const fn graph(&self) -> &UndirectedGraph<()> {
+        &self.graph
+    }
+
+    /// Get position after removing `vertex` and its neighbours
+    #[must_use]
+    pub fn remove_closed_neighbourhood(&self, vertex: VertexIndex) -> Self {
+        let remaining = self
+            .graph
+            .vertex_indices()
+            .filter(|&u| u != vertex && !self.graph.are_adjacent(vertex, u))
+            .collect::<Vec<_>>();
+        Self::new(self.graph.induced_subgraph(&remaining))
+    }
+}
+
+impl ImpartialGame for NodeKayles {
+    fn moves(&self) -> Vec<Self> {
+        self.graph
+            .vertex_indices()
+            .map(|vertex| self.remove_closed_neighbourhood(vertex))
+            .collect()
+    }
+
+    /// Connected components in canonical labeling, so isomorphic components share the nim value
+    /// in the transposition table
+    fn decompositions(&self) -> Vec<Self> {
+        canonical::canonical_components(&self.graph)
+            .into_iter()
+            .map(Self::new)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::transposition_table::ParallelTranspositionTable;
+    use crate::{numeric::nimber::Nimber, short::impartial::games::octal::Octal};
+    use std::str::FromStr;
+
+    fn path(n: usize) -> UndirectedGraph<()> {
+        let edges = (1..n)
+            .map(|index| (VertexIndex { index: index - 1 }, VertexIndex { index }))
+            .collect::<Vec<_>>();
+        UndirectedGraph::from_edges(&edges, &vec![(); n])
+    }
+
+    #[test]
+    fn dawsons_chess_on_paths() {
+        let transposition_table = ParallelTranspositionTable::new();
+        let values = Octal::from_str("0.137").unwrap().grundy_values(20);
+        for (n, value) in values.into_iter().enumerate() {
+            assert_eq!(
+                NodeKayles::new(path(n)).nim_value(&transposition_table),
+                value,
+                "P{n}"
+            );
+        }
+    }
+
+    #[test]
+    fn cycles() {
+        let transposition_table = ParallelTranspositionTable::new();
+        let values = Octal::from_str("0.137").unwrap().grundy_values(12);
+        for n in 4..=15 {
+            let mut graph = path(n);
+            graph.connect(VertexIndex { index: 0 }, VertexIndex { index: n - 1 }, true);
+
+            // Every move leaves a path of `n - 3` vertices
+            let expected = Nimber::mex(vec![values[n - 3]]);
+            assert_eq!(
+                NodeKayles::new(graph).nim_value(&transposition_table),
+                expected,
+                "C{n}"
+            );
+        }
+    }
+
+    #[test]
+    fn isomorphic_components() {
+        // Two paths of length 3 labeled differently and a single vertex
+        let graph = UndirectedGraph::from_edges(
+            &[
+                (VertexIndex { index: 0 }, VertexIndex { index: 1 }),
+                (VertexIndex { index: 1 }, VertexIndex { index: 2 }),
+                (VertexIndex { index: 3 }, VertexIndex { index: 5 }),
+                (VertexIndex { index: 5 }, VertexIndex { index: 4 }),
+            ],
+            &[(); 7],
+        );
+        let decompositions = NodeKayles::new(graph).decompositions();
+        assert_eq!(decompositions.len(), 3);
+        assert_eq!(decompositions[0], decompositions[1]);
+        assert_eq!(decompositions[2], NodeKayles::new(path(1)));
+    }
+}
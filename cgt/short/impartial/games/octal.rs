@@ -0,0 +1,305 @@
+//! Octal and hexadecimal games, played on heaps of tokens. Game is given by a code `d0.d1d2d3...`
+//! where digit `dk` describes moves that remove `k` tokens from a heap. If `dk` has bit `2^i`
+//! set, a player may remove `k` tokens and leave exactly `i` non-empty heaps. Octal games can
+//! leave up to two heaps, hexadecimal games up to three.
+
+use crate::{
+    numeric::nimber::Nimber,
+    short::impartial::{
+        grundy_sequence::{PeriodicSequence, Periodicity, Theorem},
+        heap_game::HeapGame,
+    },
+};
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// Octal or hexadecimal game given by its code
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Octal {
+    // Invariant: last digit is not zero, unless the code is empty
+    code: Vec<u8>,
+}
+
+impl Octal {
+    /// Create new game from a code, where `code[k]` is the digit describing moves that remove `k`
+    /// tokens. Returns `None` if any digit is not hexadecimal, or first digit allows moves that
+    /// do not split the heap.
+    pub fn new(mut code: Vec<u8>) -> Option<Self> {
+        if code.iter().any(|&d| d > 0xf) || code.first().is_some_and(|&d| d & 0b11 != 0) {
+            return None;
+        }
+        while code.last() == Some(&0) {
+            code.pop();
+        }
+        Some(Self { code })
+    }
+
+    /// Get the code of the game
+    #[inline]
+    pub fn code(&self) -> &[u8] {
+        &self.code
+    }
+
+    /// Check if the game is hexadecimal, i.e. some move leaves three heaps
+    pub fn is_hexadecimal(&self) -> bool {
+        self.code.iter().any(|&d| d & 0b1000 != 0)
+    }
+
+    /// Maximum number of heaps left after a single move
+    fn max_parts(&self) -> usize {
+        self.code
+            .iter()
+            .map(|&d| (8 - d.leading_zeros() as usize).saturating_sub(1))
+            .max()
+            .unwrap_or(0)
+            .max(1)
+    }
+
+    /// Get the Grundy values of heaps from `0` up to `max_heap` (inclusive)
+    pub fn grundy_values(&self, max_heap: u32) -> Vec<Nimber> {
+        self.clone()
+            .grundy_sequence()
+            .take(max_heap as usize + 1)
+            .collect()
+    }
+
+    /// Get the infinite Grundy sequence of the game
+    #[inline]
+    pub const fn grundy_sequence(self) -> GrundySequence {
+        GrundySequence {
+            game: self,
+            previous: Vec::new(),
+        }
+    }
+
+    /// Check if the Grundy sequence is periodic, using values of heaps up to `max_heap`.
+    /// Returns `None` if periodicity cannot be observed for long enough with the given number of
+    /// values. Only periodicity with zero saltus is proven.
+    pub fn periodicity(&self, max_heap: u32) -> Option<Periodicity> {
+        self.clone()
+            .grundy_sequence()
+            .detect_period(max_heap as usize)
+    }
+
+    fn for_each_move(&self, heap: u32, mut f: impl FnMut(&[u32])) {
+        for (removed, &digit) in self.code.iter().enumerate() {
+            let removed = removed as u32;
+            if removed > heap {
+                break;
+            }
+            let rest = heap - removed;
+            if digit & 0b0001 != 0 && rest == 0 {
+                f(&[]);
+            }
+            if digit & 0b0010 != 0 && rest > 0 {
+                f(&[rest]);
+            }
+            if digit & 0b0100 != 0 {
+                for a in 1..=rest / 2 {
+                    f(&[a, rest - a]);
+                }
+            }
+            if digit & 0b1000 != 0 {
+                for a in 1..=rest / 3 {
+                    for b in a..=(rest - a) / 2 {
+                        f(&[a, b, rest - a - b]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl HeapGame for Octal {
+    fn heap_moves(&self, heap: u32) -> Vec<Vec<u32>> {
+        let mut moves = Vec::new();
+        self.for_each_move(heap, |heaps| moves.push(heaps.to_vec()));
+        moves
+    }
+}
+
+impl Display for Octal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:X}", self.code.first().copied().unwrap_or(0))?;
+        if self.code.len() > 1 {
+            write!(f, ".")?;
+            for digit in &self.code[1..] {
+                write!(f, "{digit:X}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Octal {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (whole, fractional) = s.split_once('.').unwrap_or((s, ""));
+        let whole = if whole.is_empty() { "0" } else { whole };
+        if whole.len() != 1 {
+            return Err(());
+        }
+        let code = whole
+            .chars()
+            .chain(fractional.chars())
+            .map(|c| c.to_digit(16).map(|d| d as u8))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(())?;
+        Self::new(code).ok_or(())
+    }
+}
+
+/// Grundy Sequence of [Octal] iterator. Splits are computed as nim sums of values of the parts.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GrundySequence {
+    /// The underlying octal game ruleset
+    game: Octal,
+
+    /// All previous values
+    previous: Vec<Nimber>,
+}
+
+impl Iterator for GrundySequence {
+    type Item = Nimber;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let heap = self.previous.len() as u32;
+        let mut for_mex = Vec::new();
+        self.game.for_each_move(heap, |heaps| {
+            for_mex.push(
+                heaps
+                    .iter()
+                    .fold(Nimber::new(0), |acc, &h| acc + self.previous[h as usize]),
+            );
+        });
+        // Heap of size zero has no moves, as the first digit can only split a heap
+        let mex = Nimber::mex(for_mex);
+        self.previous.push(mex);
+        Some(mex)
+    }
+}
+
+impl PeriodicSequence for GrundySequence {
+    fn restarted(&self) -> Self {
+        self.game.clone().grundy_sequence()
+    }
+
+    fn required_values(&self, preperiod: usize, period: usize) -> usize {
+        let parts = self.game.max_parts();
+        let max_removed = self.game.code.len().saturating_sub(1);
+        parts * preperiod + parts * period + max_removed
+    }
+
+    fn theorem(&self, saltus: u32) -> Option<Theorem> {
+        (saltus == 0).then_some(Theorem::GuySmith)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::impartial::games::subtraction::Sub;
+
+    fn values(game: &str, max_heap: u32) -> Vec<u32> {
+        Octal::from_str(game)
+            .unwrap()
+            .grundy_values(max_heap)
+            .into_iter()
+            .map(|n| n.value())
+            .collect()
+    }
+
+    #[test]
+    fn parsing() {
+        for code in ["0.137", "4.07", "0.77", "0.F", "8.3C"] {
+            assert_eq!(Octal::from_str(code).unwrap().to_string(), code);
+        }
+        assert_eq!(Octal::from_str("0.370").unwrap().to_string(), "0.37");
+        assert_eq!(Octal::from_str(".6").unwrap().to_string(), "0.6");
+        assert!(Octal::from_str("0.f").unwrap().is_hexadecimal());
+        assert!(Octal::from_str("1.3").is_err());
+        assert!(Octal::from_str("0.1G").is_err());
+        assert!(Octal::from_str("40.7").is_err());
+    }
+
+    #[test]
+    fn grundy_values() {
+        // Kayles
+        assert_eq!(values("0.77", 11), [0, 1, 2, 3, 1, 4, 3, 2, 1, 4, 2, 6]);
+        // Dawson's Kayles is Dawson's Chess shifted by one
+        assert_eq!(values("0.07", 201)[1..], values("0.137", 200));
+
+        let sub = Sub::new(vec![1, 2, 3])
+            .grundy_sequence()
+            .take(50)
+            .collect::<Vec<_>>();
+        assert_eq!(Octal::from_str("0.333").unwrap().grundy_values(49), sub);
+    }
+
+    #[test]
+    fn heap_moves() {
+        fn nim_value(game: &Octal, heaps: &[u32]) -> Nimber {
+            heaps
+                .iter()
+                .map(|&heap| {
+                    Nimber::mex(
+                        game.heap_moves(heap)
+                            .iter()
+                            .map(|m| nim_value(game, m))
+                            .collect(),
+                    )
+                })
+                .fold(Nimber::new(0), |acc, n| acc + n)
+        }
+
+        for code in ["0.77", "4.07", "0.F", "0.137"] {
+            let game = Octal::from_str(code).unwrap();
+            let values = game.grundy_values(12);
+            for heap in 0..=12 {
+                assert_eq!(nim_value(&game, &[heap]), values[heap as usize]);
+            }
+        }
+    }
+
+    #[test]
+    fn periodicity() {
+        let kayles = Octal::from_str("0.77").unwrap();
+        assert_eq!(kayles.periodicity(100), None);
+        let periodicity = kayles.periodicity(200).unwrap();
+        assert_eq!(periodicity.preperiod(), 71);
+        assert_eq!(periodicity.period(), 12);
+        assert_eq!(
+            periodicity.certificate().map(|c| c.theorem()),
+            Some(Theorem::GuySmith)
+        );
+        assert!(periodicity.verify(&kayles.grundy_values(200)));
+
+        let dawson = Octal::from_str("0.137").unwrap();
+        let periodicity = dawson.periodicity(300).unwrap();
+        assert_eq!(periodicity.preperiod(), 52);
+        assert_eq!(periodicity.period(), 34);
+
+        let values = dawson.grundy_values(1000);
+        assert!((0..=1000).all(|n| periodicity.value(&values, n) == values[n]));
+
+        let sub = Octal::from_str("0.33").unwrap().periodicity(10).unwrap();
+        assert_eq!(sub.to_string(), "preperiod 0, period 3");
+
+        // Arithmetic periodicity is reported, but not proven
+        let game = Octal::from_str("0.C").unwrap();
+        let periodicity = game.periodicity(50).unwrap();
+        assert_eq!(
+            periodicity.to_string(),
+            "preperiod 1, period 2, saltus 1, not proven"
+        );
+        assert_eq!(periodicity.certificate(), None);
+        let values = game.grundy_values(300);
+        assert!(!periodicity.verify(&values));
+        assert!((0..=300).all(|n| periodicity.value(&values, n) == values[n]));
+    }
+}
@@ -7,11 +7,14 @@
 //!
 //! This game has been proposed in [Andreas Chen's "The Quicksort Game"](https://www.diva-portal.org/smash/get/diva2:935354/FULLTEXT01.pdf>).
 
-use crate::{display, short::impartial::impartial_game::ImpartialGame};
+use crate::{
+    display,
+    short::impartial::{games::quicksort::sorted_blocks, impartial_game::ImpartialGame},
+};
 use std::fmt::Display;
 
 /// See [`pseudo_quickcheck`](self) header
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PseudoQuicksort {
     sequence: Vec<u32>,
@@ -66,27 +69,36 @@ impl ImpartialGame for PseudoQuicksort {
         }
         res
     }
+
+    fn decompositions(&self) -> Vec<Self> {
+        sorted_blocks(self.sequence())
+            .into_iter()
+            .map(Self::new)
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::numeric::nimber::Nimber;
+    use crate::short::partizan::transposition_table::ParallelTranspositionTable;
 
     #[test]
     fn correct_nim_value() {
+        let transposition_table = ParallelTranspositionTable::new();
         assert_eq!(
-            PseudoQuicksort::new(vec![1, 2, 3, 6, 5, 4]).nim_value(),
+            PseudoQuicksort::new(vec![1, 2, 3, 6, 5, 4]).nim_value(&transposition_table),
             Nimber::new(0)
         );
 
         assert_eq!(
-            PseudoQuicksort::new(vec![4, 1, 6, 5, 7, 3, 8, 2]).nim_value(),
+            PseudoQuicksort::new(vec![4, 1, 6, 5, 7, 3, 8, 2]).nim_value(&transposition_table),
             Nimber::new(5)
         );
 
         assert_eq!(
-            PseudoQuicksort::new(vec![4, 1, 6, 5, 7, 8, 2, 3]).nim_value(),
+            PseudoQuicksort::new(vec![4, 1, 6, 5, 7, 8, 2, 3]).nim_value(&transposition_table),
             Nimber::new(3)
         );
     }
@@ -2,7 +2,7 @@
 //! a number in the manner of the quicksort algorithm.
 //!
 //! This is impartial version of the game, where both players can pick both even and odd pivots.
-//! For partizan version see TODO.
+//! For partizan version see [`partizan_quicksort`](crate::short::partizan::games::partizan_quicksort).
 //!
 //! This game has been proposed in [Andreas Chen's "The Quicksort Game"](https://www.diva-portal.org/smash/get/diva2:935354/FULLTEXT01.pdf>).
 
@@ -11,7 +11,7 @@ use std::fmt::Display;
 use crate::{display, short::impartial::impartial_game::ImpartialGame};
 
 /// See [quickcheck](self) header
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Quicksort {
     sequence: Vec<u32>,
@@ -60,6 +60,47 @@ impl Quicksort {
     }
 }
 
+/// Split sequence into blocks such that every element of a block is smaller than all elements of
+/// the following blocks. Pivoting never moves elements between such blocks, so they are
+/// independent components.
+pub(crate) fn sorted_block_slices(sequence: &[u32]) -> Vec<&[u32]> {
+    let mut suffix_min = vec![u32::MAX; sequence.len() + 1];
+    for (idx, elem) in sequence.iter().enumerate().rev() {
+        suffix_min[idx] = suffix_min[idx + 1].min(*elem);
+    }
+
+    let mut blocks = Vec::new();
+    let mut block_start = 0;
+    let mut prefix_max = 0;
+    for (idx, elem) in sequence.iter().enumerate() {
+        prefix_max = prefix_max.max(*elem);
+        if prefix_max < suffix_min[idx + 1] {
+            blocks.push(&sequence[block_start..=idx]);
+            block_start = idx + 1;
+        }
+    }
+    blocks
+}
+
+/// Split sequence into [sorted blocks](sorted_block_slices). Blocks are renumbered to `1..=n` as
+/// only the relative order matters, and blocks of a single element are dropped as they have no
+/// moves.
+pub(crate) fn sorted_blocks(sequence: &[u32]) -> Vec<Vec<u32>> {
+    sorted_block_slices(sequence)
+        .into_iter()
+        .filter(|block| block.len() > 1)
+        .map(|block| {
+            let mut ranks = block.to_vec();
+            ranks.sort_unstable();
+            ranks.dedup();
+            block
+                .iter()
+                .map(|elem| ranks.binary_search(elem).unwrap() as u32 + 1)
+                .collect()
+        })
+        .collect()
+}
+
 impl ImpartialGame for Quicksort {
     fn moves(&self) -> Vec<Self> {
         let mut moves = Vec::with_capacity(self.sequence().len());
@@ -71,27 +112,36 @@ impl ImpartialGame for Quicksort {
         }
         moves
     }
+
+    fn decompositions(&self) -> Vec<Self> {
+        sorted_blocks(self.sequence())
+            .into_iter()
+            .map(Self::new)
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::numeric::nimber::Nimber;
+    use crate::short::partizan::transposition_table::ParallelTranspositionTable;
 
     #[test]
     fn correct_nim_value() {
+        let transposition_table = ParallelTranspositionTable::new();
         assert_eq!(
-            Quicksort::new(vec![1, 2, 3, 6, 5, 4]).nim_value(),
+            Quicksort::new(vec![1, 2, 3, 6, 5, 4]).nim_value(&transposition_table),
             Nimber::new(2)
         );
 
         assert_eq!(
-            Quicksort::new(vec![4, 1, 6, 5, 7, 3, 8, 2]).nim_value(),
+            Quicksort::new(vec![4, 1, 6, 5, 7, 3, 8, 2]).nim_value(&transposition_table),
             Nimber::new(5)
         );
 
         assert_eq!(
-            Quicksort::new(vec![4, 1, 6, 5, 7, 8, 2, 3]).nim_value(),
+            Quicksort::new(vec![4, 1, 6, 5, 7, 8, 2, 3]).nim_value(&transposition_table),
             Nimber::new(0)
         );
     }
@@ -99,11 +149,30 @@ mod tests {
     /// Sequence in form of 2,3,4,...,n,1 has nim-value of *(n-1)
     #[test]
     fn one_end_hypothesis() {
+        let transposition_table = ParallelTranspositionTable::new();
         for end in 2..16 {
             let mut sequence = (2..=end).collect::<Vec<u32>>();
             sequence.push(1);
             let quicksort = Quicksort::new(sequence);
-            assert_eq!(quicksort.nim_value(), Nimber::new(end - 1));
+            assert_eq!(
+                quicksort.nim_value(&transposition_table),
+                Nimber::new(end - 1)
+            );
         }
     }
+
+    #[test]
+    fn decomposes_into_blocks() {
+        let transposition_table = ParallelTranspositionTable::new();
+        let quicksort = Quicksort::new(vec![2, 1, 3, 6, 4, 5, 7]);
+        assert_eq!(
+            quicksort.decompositions(),
+            vec![Quicksort::new(vec![2, 1]), Quicksort::new(vec![3, 1, 2])]
+        );
+        assert_eq!(
+            quicksort.nim_value(&transposition_table),
+            Nimber::new(1) + Nimber::new(2)
+        );
+        assert_eq!(Quicksort::new(vec![1, 2, 3]).decompositions(), vec![]);
+    }
 }
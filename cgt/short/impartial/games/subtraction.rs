@@ -2,7 +2,14 @@
 
 use std::fmt::Display;
 
-use crate::{display, numeric::nimber::Nimber};
+use crate::{
+    display,
+    numeric::nimber::Nimber,
+    short::impartial::{
+        grundy_sequence::{PeriodicSequence, Theorem},
+        heap_game::HeapGame,
+    },
+};
 
 /// Subtraction game played on an arbitrary finite subtraction set
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -47,6 +54,16 @@ impl Sub {
     }
 }
 
+impl HeapGame for Sub {
+    fn heap_moves(&self, heap: u32) -> Vec<Vec<u32>> {
+        self.subtraction_set()
+            .iter()
+            .take_while(|&&m| m <= heap)
+            .map(|m| if heap == *m { vec![] } else { vec![heap - m] })
+            .collect()
+    }
+}
+
 /// Grundy Sequence of [Sub] iterator using Grundy scale method.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -86,6 +103,23 @@ impl Iterator for GrundySequence {
     }
 }
 
+impl PeriodicSequence for GrundySequence {
+    fn restarted(&self) -> Self {
+        self.game.clone().grundy_sequence()
+    }
+
+    fn required_values(&self, preperiod: usize, period: usize) -> usize {
+        let largest = self.game.subtraction_set().last().copied().unwrap_or(0) as usize;
+        preperiod + period + largest
+    }
+
+    fn theorem(&self, saltus: u32) -> Option<Theorem> {
+        // Finite subtraction games are always periodic with saltus 0, so only the zero saltus
+        // case is covered by a theorem
+        (saltus == 0).then_some(Theorem::SubtractionGame)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,4 +155,41 @@ mod tests {
         assert_grundy!([5], [0, 0, 0, 0, 0, 1, 1, 1, 1, 1], 10);
         assert_grundy!([2, 3, 5], [0, 0, 1, 1, 2, 2, 3], 7);
     }
+
+    #[test]
+    fn detects_period() {
+        for (subtraction_set, preperiod, period) in [
+            (vec![1], 0, 2),
+            (vec![5], 0, 10),
+            (vec![2, 3, 5], 0, 7),
+            (vec![1, 4, 5], 0, 8),
+            (vec![3, 5, 9], 14, 2),
+        ] {
+            let periodicity = Sub::new(subtraction_set)
+                .grundy_sequence()
+                .detect_period(100)
+                .unwrap();
+            assert_eq!(periodicity.preperiod(), preperiod);
+            assert_eq!(periodicity.period(), period);
+            assert_eq!(periodicity.saltus(), 0);
+            assert_eq!(
+                periodicity.certificate().map(|c| c.theorem()),
+                Some(Theorem::SubtractionGame)
+            );
+        }
+
+        // Period must repeat for the largest subtraction to be proven
+        assert_eq!(
+            Sub::new(vec![2, 3, 5]).grundy_sequence().detect_period(10),
+            None
+        );
+
+        // Values are computed from the start
+        let mut sequence = Sub::new(vec![2, 3, 5]).grundy_sequence();
+        sequence.nth(20);
+        assert_eq!(
+            sequence.detect_period(100),
+            Sub::new(vec![2, 3, 5]).grundy_sequence().detect_period(100)
+        );
+    }
 }
@@ -0,0 +1,192 @@
+//! Grundy sequences of heap games and their periodicity
+
+use crate::numeric::nimber::Nimber;
+use std::fmt::{self, Display};
+
+/// Theorem used to prove that a Grundy sequence is periodic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Theorem {
+    /// Subtraction game with largest subtraction `k` is periodic if `G(n + p) = G(n)` for all
+    /// `e <= n < e + k`
+    SubtractionGame,
+
+    /// Octal game removing at most `k` tokens is periodic if `G(n + p) = G(n)` for all
+    /// `e <= n < 2e + p + k`. Hexadecimal games use bound `3e + 2p + k`.
+    GuySmith,
+}
+
+/// Proof that periodicity observed on a prefix of the sequence holds forever
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Certificate {
+    theorem: Theorem,
+    values: usize,
+}
+
+impl Certificate {
+    /// Create new certificate, stating that checking the first `values` values is enough to
+    /// prove periodicity
+    #[inline]
+    pub const fn new(theorem: Theorem, values: usize) -> Self {
+        Self { theorem, values }
+    }
+
+    /// Theorem used in the proof
+    #[inline]
+    pub const fn theorem(&self) -> Theorem {
+        self.theorem
+    }
+
+    /// Number of values, starting from heap of size zero, that must follow the period
+    #[inline]
+    pub const fn values(&self) -> usize {
+        self.values
+    }
+}
+
+/// Arithmetic periodicity of a Grundy sequence, i.e. `G(n + period) = G(n) + saltus` for all
+/// `n >= preperiod`, where `+` is the ordinary addition. Sequences with zero saltus are periodic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Periodicity {
+    preperiod: usize,
+    period: usize,
+    saltus: u32,
+    certificate: Option<Certificate>,
+}
+
+impl Periodicity {
+    /// Length of the irregular part before the sequence becomes periodic
+    #[inline]
+    pub const fn preperiod(&self) -> usize {
+        self.preperiod
+    }
+
+    /// Length of the period
+    #[inline]
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Difference between values that are one period apart
+    #[inline]
+    pub const fn saltus(&self) -> u32 {
+        self.saltus
+    }
+
+    /// Proof of the periodicity, or `None` if it was only observed
+    #[inline]
+    pub const fn certificate(&self) -> Option<Certificate> {
+        self.certificate
+    }
+
+    /// Check if the periodicity is proven to hold for all heaps
+    #[inline]
+    pub const fn is_proven(&self) -> bool {
+        self.certificate.is_some()
+    }
+
+    /// Check that the values follow the periodicity as far as required by the certificate.
+    /// Periodicity without a certificate cannot be verified.
+    pub fn verify(&self, values: &[Nimber]) -> bool {
+        self.certificate.is_some_and(|certificate| {
+            values.len() >= certificate.values
+                && (self.preperiod..certificate.values.saturating_sub(self.period))
+                    .all(|n| values[n + self.period].value() == values[n].value() + self.saltus)
+        })
+    }
+
+    /// Get the Grundy value of any heap, given at least `preperiod + period` first values
+    pub fn value(&self, values: &[Nimber], heap: usize) -> Nimber {
+        if heap < self.preperiod + self.period {
+            return values[heap];
+        }
+        let offset = heap - self.preperiod;
+        let periods = (offset / self.period) as u32;
+        Nimber::new(values[self.preperiod + offset % self.period].value() + periods * self.saltus)
+    }
+}
+
+impl Display for Periodicity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "preperiod {}, period {}", self.preperiod, self.period)?;
+        if self.saltus != 0 {
+            write!(f, ", saltus {}", self.saltus)?;
+        }
+        if !self.is_proven() {
+            write!(f, ", not proven")?;
+        }
+        Ok(())
+    }
+}
+
+/// Grundy sequence of a heap game whose periodicity can be checked from finitely many values
+pub trait PeriodicSequence: Iterator<Item = Nimber> + Sized {
+    /// Get the same sequence, starting again from heap of size zero
+    #[must_use]
+    fn restarted(&self) -> Self;
+
+    /// Number of values, starting from heap of size zero, that must follow the periodicity for
+    /// it to be reported
+    fn required_values(&self, preperiod: usize, period: usize) -> usize;
+
+    /// Theorem proving periodicity with given saltus from the required values, or `None` if no
+    /// theorem applies to it
+    fn theorem(&self, saltus: u32) -> Option<Theorem>;
+
+    /// Find the shortest period that holds for the required number of values, using values of
+    /// heaps up to `max_heap`. Periodicity is certified if a theorem applies to it, otherwise it
+    /// is only observed. Values are computed from the start, regardless of the current position
+    /// of the sequence.
+    fn detect_period(&self, max_heap: usize) -> Option<Periodicity> {
+        let values = self.restarted().take(max_heap + 1).collect::<Vec<_>>();
+        let (preperiod, period, saltus) = find_period(
+            &values,
+            |previous, next| next.value().checked_sub(previous.value()),
+            |preperiod, period, _| self.required_values(preperiod, period) <= values.len(),
+        )?;
+        let certificate = self
+            .theorem(saltus)
+            .map(|theorem| Certificate::new(theorem, self.required_values(preperiod, period)));
+        Some(Periodicity {
+            preperiod,
+            period,
+            saltus,
+            certificate,
+        })
+    }
+}
+
+/// Find the shortest period of `values`, together with the smallest preperiod and the saltus,
+/// that is accepted by `accept`. Saltus is the `difference` between values one period apart, or
+/// `None` if values cannot follow a period with such difference.
+pub(crate) fn find_period<T, S>(
+    values: &[T],
+    difference: impl Fn(&T, &T) -> Option<S>,
+    accept: impl Fn(usize, usize, &S) -> bool,
+) -> Option<(usize, usize, S)>
+where
+    S: PartialEq,
+{
+    let last = values.len().checked_sub(1)?;
+    for period in 1..values.len() {
+        let Some(saltus) = difference(&values[last - period], &values[last]) else {
+            continue;
+        };
+
+        // Smallest preperiod such that the period holds in the computed values
+        let mut preperiod = values.len() - period;
+        while preperiod > 0
+            && difference(&values[preperiod - 1], &values[preperiod - 1 + period]).as_ref()
+                == Some(&saltus)
+        {
+            preperiod -= 1;
+        }
+
+        if accept(preperiod, period, &saltus) {
+            return Some((preperiod, period, saltus));
+        }
+    }
+    None
+}
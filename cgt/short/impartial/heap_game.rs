@@ -0,0 +1,84 @@
+//! Heap game - impartial game played on a collection of heaps where every move changes a single heap
+
+use crate::{display, short::impartial::impartial_game::ImpartialGame};
+use std::{
+    fmt::{self, Display},
+    hash::Hash,
+};
+
+/// Heap game ruleset
+pub trait HeapGame {
+    /// Get a list of moves from a single heap. Every move is a list of non-empty heaps that
+    /// replace the heap.
+    fn heap_moves(&self, heap: u32) -> Vec<Vec<u32>>;
+}
+
+/// Position of a heap game, i.e. ruleset with a collection of heaps
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeapPosition<R> {
+    ruleset: R,
+    // Invariant: sorted, without empty heaps
+    heaps: Vec<u32>,
+}
+
+impl<R> HeapPosition<R> {
+    /// Create new position with given heaps
+    #[inline]
+    pub fn new(ruleset: R, mut heaps: Vec<u32>) -> Self {
+        heaps.retain(|&heap| heap != 0);
+        heaps.sort_unstable();
+        Self { ruleset, heaps }
+    }
+
+    /// Get the ruleset of the position
+    #[inline]
+    pub const fn ruleset(&self) -> &R {
+        &self.ruleset
+    }
+
+    /// Get the non-empty heaps of the position in ascending order
+    #[inline]
+    pub fn heaps(&self) -> &[u32] {
+        &self.heaps
+    }
+}
+
+impl<R> Display for HeapPosition<R>
+where
+    R: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ruleset)?;
+        display::brackets(f, |f| display::commas(f, self.heaps()))
+    }
+}
+
+impl<R> ImpartialGame for HeapPosition<R>
+where
+    R: HeapGame + Clone + Hash + Send + Sync + Eq,
+{
+    fn moves(&self) -> Vec<Self> {
+        let mut moves = Vec::new();
+        for (idx, &heap) in self.heaps.iter().enumerate() {
+            // Heaps are sorted so moves from equal heaps are the same
+            if idx > 0 && self.heaps[idx - 1] == heap {
+                continue;
+            }
+            for replacement in self.ruleset.heap_moves(heap) {
+                let mut heaps = self.heaps.clone();
+                heaps.remove(idx);
+                heaps.extend(replacement);
+                moves.push(Self::new(self.ruleset.clone(), heaps));
+            }
+        }
+        moves
+    }
+
+    fn decompositions(&self) -> Vec<Self> {
+        self.heaps
+            .iter()
+            .map(|&heap| Self::new(self.ruleset.clone(), vec![heap]))
+            .collect()
+    }
+}
@@ -1,19 +1,66 @@
 //! Impartial game - both players have the same moves
 
-use crate::numeric::nimber::Nimber;
+use crate::{
+    misere::genus::Genus, numeric::nimber::Nimber,
+    short::partizan::transposition_table::TranspositionTable,
+};
+use std::hash::Hash;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 
 /// Impartial game
-pub trait ImpartialGame: Sized {
+pub trait ImpartialGame: Sized + Clone + Hash + Send + Sync + Eq {
     /// Get a list of moves from the position
     fn moves(&self) -> Vec<Self>;
 
-    /// Calculate the Nim value of the position
-    fn nim_value(&self) -> Nimber {
-        let moves = self.moves();
-        let mut game_moves = Vec::with_capacity(moves.len());
-        for m in moves {
-            game_moves.push(m.nim_value());
+    /// Split game into disjoint sum
+    ///
+    /// Note that default implementation doesn't find any decompisitons and may lead to performance
+    /// issues
+    fn decompositions(&self) -> Vec<Self> {
+        vec![self.clone()]
+    }
+
+    /// Calculate the Nim value of the position, caching values of visited positions in the
+    /// transposition table
+    fn nim_value<TT>(&self, transposition_table: &TT) -> Nimber
+    where
+        TT: TranspositionTable<Self, Nimber> + Sync,
+    {
+        if let Some(value) = transposition_table.lookup_position(self) {
+            return value;
         }
-        Nimber::mex(game_moves)
+
+        #[cfg(feature = "rayon")]
+        let decompositions = self.decompositions().into_par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let decompositions = self.decompositions().into_iter();
+
+        // Nim value of a sum is the nim sum of values of its components
+        let result: Nimber = decompositions
+            .map(|position| {
+                transposition_table
+                    .lookup_position(&position)
+                    .unwrap_or_else(|| {
+                        #[cfg(feature = "rayon")]
+                        let moves = position.moves().into_par_iter();
+                        #[cfg(not(feature = "rayon"))]
+                        let moves = position.moves().into_iter();
+
+                        let value =
+                            Nimber::mex(moves.map(|m| m.nim_value(transposition_table)).collect());
+                        transposition_table.insert_position(position, value);
+                        value
+                    })
+            })
+            .sum();
+        transposition_table.insert_position(self.clone(), result);
+        result
+    }
+
+    /// Calculate the genus of the position, describing its misère play behavior
+    fn genus(&self) -> Genus {
+        Genus::new(self)
     }
 }
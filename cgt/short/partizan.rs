@@ -1,8 +1,16 @@
 //! Partizan games
 
+pub mod cake_game;
 pub mod canonical_form;
+pub mod combinators;
+pub mod game_tree;
 pub mod games;
+pub mod outcome;
 pub mod partizan_game;
+pub mod stone;
+pub mod strategy;
+pub mod strip;
+pub mod symmetry;
 pub mod thermograph;
 pub mod trajectory;
 pub mod transposition_table;
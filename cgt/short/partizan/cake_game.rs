@@ -0,0 +1,200 @@
+//! Cake game - partizan game played on a collection of rectangular pieces of cake where every
+//! move changes a single piece
+
+use crate::{
+    display,
+    drawing::{BoundingBox, Canvas, Color, Draw},
+    numeric::v2f::V2f,
+    short::partizan::{Player, canonical_form::CanonicalForm, partizan_game::PartizanGame},
+};
+use std::{
+    fmt::{self, Display},
+    hash::Hash,
+};
+
+/// Rectangular piece of cake
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rectangle {
+    /// Number of columns
+    pub width: u32,
+
+    /// Number of rows
+    pub height: u32,
+}
+
+impl Rectangle {
+    /// Create new rectangle
+    #[inline]
+    pub const fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+
+    /// Swap width and height
+    #[inline]
+    #[must_use]
+    pub const fn transpose(self) -> Self {
+        Self::new(self.height, self.width)
+    }
+
+    /// Check if rectangle has no squares
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+}
+
+impl Display for Rectangle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+/// Cake game ruleset
+pub trait CakeGame {
+    /// Get a list of moves of a player from a single piece. Every move is a list of pieces that
+    /// replace the piece.
+    fn piece_moves(&self, piece: Rectangle, player: Player) -> Vec<Vec<Rectangle>>;
+
+    /// Get value of a single piece, if closed form is known
+    fn piece_value(&self, _piece: Rectangle) -> Option<CanonicalForm> {
+        None
+    }
+}
+
+/// Position of a cake game, i.e. ruleset with a collection of pieces
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CakePosition<R> {
+    ruleset: R,
+    // Invariant: sorted, without empty pieces
+    pieces: Vec<Rectangle>,
+}
+
+impl<R> CakePosition<R> {
+    /// Create new position with given pieces
+    #[inline]
+    pub fn new(ruleset: R, mut pieces: Vec<Rectangle>) -> Self {
+        pieces.retain(|piece| !piece.is_empty());
+        pieces.sort_unstable();
+        Self { ruleset, pieces }
+    }
+
+    /// Get the ruleset of the position
+    #[inline]
+    pub const fn ruleset(&self) -> &R {
+        &self.ruleset
+    }
+
+    /// Get the non-empty pieces of the position in ascending order
+    #[inline]
+    pub fn pieces(&self) -> &[Rectangle] {
+        &self.pieces
+    }
+}
+
+impl<R> CakePosition<R>
+where
+    R: CakeGame + Clone,
+{
+    fn moves_for(&self, player: Player) -> Vec<Self> {
+        let mut moves = Vec::new();
+        for (idx, &piece) in self.pieces.iter().enumerate() {
+            // Pieces are sorted so moves from equal pieces are the same
+            if idx > 0 && self.pieces[idx - 1] == piece {
+                continue;
+            }
+            for replacement in self.ruleset.piece_moves(piece, player) {
+                let mut pieces = self.pieces.clone();
+                pieces.remove(idx);
+                pieces.extend(replacement);
+                moves.push(Self::new(self.ruleset.clone(), pieces));
+            }
+        }
+        moves
+    }
+}
+
+impl<R> Display for CakePosition<R>
+where
+    R: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ruleset)?;
+        display::brackets(f, |f| display::commas(f, self.pieces()))
+    }
+}
+
+impl<R> PartizanGame for CakePosition<R>
+where
+    R: CakeGame + Clone + Hash + Send + Sync + Eq,
+{
+    fn left_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Left)
+    }
+
+    fn right_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Right)
+    }
+
+    fn decompositions(&self) -> Vec<Self> {
+        self.pieces
+            .iter()
+            .map(|&piece| Self::new(self.ruleset.clone(), vec![piece]))
+            .collect()
+    }
+
+    fn reductions(&self) -> Option<CanonicalForm> {
+        self.pieces
+            .iter()
+            .map(|&piece| self.ruleset.piece_value(piece))
+            .sum()
+    }
+}
+
+impl<R> Draw for CakePosition<R> {
+    fn draw<C>(&self, canvas: &mut C)
+    where
+        C: Canvas,
+    {
+        // Pieces are placed in a single row, separated by a gap of one tile
+        let mut offset = V2f::ZERO;
+        for piece in &self.pieces {
+            for y in 0..piece.height {
+                for x in 0..piece.width {
+                    canvas.rect(
+                        offset + C::wide_tile_position(u64::from(x), u64::from(y)),
+                        C::tile_size(),
+                        Color::LIGHT_GRAY,
+                    );
+                }
+            }
+            canvas.grid(offset, piece.width, piece.height);
+            offset.x += C::wide_tile_position(u64::from(piece.width) + 1, 0).x;
+        }
+    }
+
+    fn required_canvas<C>(&self) -> BoundingBox
+    where
+        C: Canvas,
+    {
+        let width = self
+            .pieces
+            .iter()
+            .map(|piece| C::wide_tile_position(u64::from(piece.width) + 1, 0).x)
+            .sum::<f32>();
+        let height = self
+            .pieces
+            .iter()
+            .map(|piece| C::wide_tile_position(0, u64::from(piece.height)).y)
+            .fold(0.0, f32::max);
+
+        BoundingBox {
+            top_left: V2f::ZERO,
+            bottom_right: V2f {
+                x: width,
+                y: height,
+            },
+        }
+    }
+}
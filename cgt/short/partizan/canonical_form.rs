@@ -2,9 +2,10 @@
 
 use crate::{
     display,
+    encoding::Encode,
     numeric::{dyadic_rational_number::DyadicRationalNumber, nimber::Nimber, rational::Rational},
     parsing::{Parser, impl_from_str_via_parser, lexeme, try_option},
-    short::partizan::{Player, thermograph::Thermograph},
+    short::partizan::{Player, outcome::Outcome, thermograph::Thermograph, trajectory::Trajectory},
     total::{TotalWrapper, impl_total_wrapper},
 };
 use auto_ops::impl_op_ex;
@@ -461,6 +462,23 @@ pub enum Temper {
     Odd,
 }
 
+/// Classification of a position by who keeps the initiative after playing in it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tempo {
+    /// Both players lose the initiative after moving, both thermograph walls slant into the mast
+    Gote,
+
+    /// Right has to answer Left's move, left wall of the thermograph is vertical below the mast
+    LeftSente,
+
+    /// Left has to answer Right's move, right wall of the thermograph is vertical below the mast
+    RightSente,
+
+    /// Position has temperature zero, so the only thing at stake is the last move
+    Tedomari,
+}
+
 /// Canonical game form
 ///
 /// Note that ordering is defined structurally for the sake of data structures. For proper partial
@@ -519,15 +537,16 @@ impl CanonicalForm {
         match &self.inner {
             CanonicalFormInner::Nus(nus) => Self::new_nus(-nus),
             CanonicalFormInner::Moves(moves) => {
+                // -{G^L | G^R} = {-G^R | -G^L}
                 let new_left_moves = moves
-                    .left
+                    .right
                     .iter()
-                    .map(|gl| Self::construct_negative(gl))
+                    .map(|gr| Self::construct_negative(gr))
                     .collect::<Vec<_>>();
                 let new_right_moves = moves
-                    .right
+                    .left
                     .iter()
-                    .map(|gr| Self::construct_negative(gr))
+                    .map(|gl| Self::construct_negative(gl))
                     .collect::<Vec<_>>();
                 let new_moves = Moves {
                     left: TotalWrapper::from_inner_vec(new_left_moves),
@@ -724,6 +743,12 @@ impl CanonicalForm {
         true
     }
 
+    /// Outcome class of the game, found by comparing it with zero
+    pub fn outcome(&self) -> Outcome {
+        let zero = Self::new_integer(0);
+        Outcome::from_first_player_wins(!Self::leq(self, &zero), !Self::leq(&zero, self))
+    }
+
     /// Calculate temperature of the game. Avoids computing a thermograph is game is a NUS
     #[allow(clippy::missing_panics_doc)]
     pub fn temperature(&self) -> DyadicRationalNumber {
@@ -820,6 +845,96 @@ impl CanonicalForm {
         }
     }
 
+    /// Left incentives `G^L - G`, i.e. how much Left gains by moving
+    pub fn left_incentives(&self) -> Vec<Self> {
+        self.left_moves()
+            .map(|left_move| &*left_move - self)
+            .collect()
+    }
+
+    /// Right incentives `G - G^R`, i.e. how much Right gains by moving
+    pub fn right_incentives(&self) -> Vec<Self> {
+        self.right_moves()
+            .map(|right_move| self - &*right_move)
+            .collect()
+    }
+
+    /// Left moves in a sum of components whose incentives are not dominated by any other
+    /// incentive in the sum. Moves are given as index of the component and its new value.
+    pub fn dominant_left_moves(components: &[Self]) -> Vec<(usize, Self)> {
+        dominant_by_incentive(components.iter().enumerate().flat_map(|(idx, component)| {
+            component.left_moves().map(move |left_move| {
+                (
+                    (idx, left_move.clone().into_owned()),
+                    &*left_move - component,
+                )
+            })
+        }))
+    }
+
+    /// Right moves in a sum of components whose incentives are not dominated by any other
+    /// incentive in the sum. Moves are given as index of the component and its new value.
+    pub fn dominant_right_moves(components: &[Self]) -> Vec<(usize, Self)> {
+        dominant_by_incentive(components.iter().enumerate().flat_map(|(idx, component)| {
+            component.right_moves().map(move |right_move| {
+                (
+                    (idx, right_move.clone().into_owned()),
+                    component - &*right_move,
+                )
+            })
+        }))
+    }
+
+    /// Left moves that are orthodox at ambient `temperature`, i.e. maximize the right wall of
+    /// their thermograph at that temperature
+    pub fn orthodox_left_moves(&self, temperature: DyadicRationalNumber) -> Vec<Self> {
+        orthodox_moves(
+            self.left_moves().map(|left_move| {
+                let thermograph = left_move.thermograph();
+                (left_move.into_owned(), thermograph)
+            }),
+            Player::Left,
+            temperature.to_rational(),
+        )
+    }
+
+    /// Right moves that are orthodox at ambient `temperature`, i.e. minimize the left wall of
+    /// their thermograph at that temperature
+    pub fn orthodox_right_moves(&self, temperature: DyadicRationalNumber) -> Vec<Self> {
+        orthodox_moves(
+            self.right_moves().map(|right_move| {
+                let thermograph = right_move.thermograph();
+                (right_move.into_owned(), thermograph)
+            }),
+            Player::Right,
+            temperature.to_rational(),
+        )
+    }
+
+    /// Classify the position as gote, sente or tedomari using its thermograph. Returns `None`
+    /// for numbers.
+    pub fn tempo(&self) -> Option<Tempo> {
+        if self.is_number() {
+            return None;
+        }
+
+        let temperature = self.temperature();
+        if temperature == DyadicRationalNumber::from(0) {
+            return Some(Tempo::Tedomari);
+        }
+
+        let thermograph = self.thermograph();
+        let temperature = temperature.to_rational();
+        match (
+            is_vertical_below(&thermograph.left_wall, temperature),
+            is_vertical_below(&thermograph.right_wall, temperature),
+        ) {
+            (true, false) => Some(Tempo::LeftSente),
+            (false, true) => Some(Tempo::RightSente),
+            _ => Some(Tempo::Gote),
+        }
+    }
+
     /// Cool the position by `temperature`
     ///
     /// Position `G` cooled by `t` is `G_t = {G^L_t - t | G^R_t + t}` unless there exists a
@@ -886,6 +1001,78 @@ impl CanonicalForm {
         new_moves.canonical_form()
     }
 
+    /// Ordinal sum `G:H = {G^L, G:H^L | G^R, G:H^R}`, where moving in `G` removes `H`
+    #[must_use]
+    pub fn ordinal_sum(&self, rhs: &Self) -> Self {
+        let left_moves = self
+            .left_moves()
+            .map(Cow::into_owned)
+            .chain(rhs.left_moves().map(|rhs_l| self.ordinal_sum(&rhs_l)))
+            .collect();
+        let right_moves = self
+            .right_moves()
+            .map(Cow::into_owned)
+            .chain(rhs.right_moves().map(|rhs_r| self.ordinal_sum(&rhs_r)))
+            .collect();
+        Self::new_from_moves(left_moves, right_moves)
+    }
+
+    /// Integer multiple of a game, i.e. sum of `multiple` copies of the game
+    fn integer_multiple(&self, multiple: i64) -> Self {
+        let sum = (0..multiple.unsigned_abs()).map(|_| self).sum::<Self>();
+        if multiple < 0 { -sum } else { sum }
+    }
+
+    /// Norton product `G.U`, equal to `n` copies of `U` if `G = n` is an integer, or
+    /// `{G^L.U + U + I | G^R.U - U - I}` otherwise, where `I` ranges over incentives of `U`.
+    /// Unit `U` should be positive. Alias for [`Mul`](std::ops::Mul) operator
+    #[must_use]
+    pub fn norton_product(&self, unit: &Self) -> Self {
+        if let Some(integer) = self.to_number().and_then(|n| n.to_integer()) {
+            return unit.integer_multiple(integer);
+        }
+
+        let incentives = unit
+            .left_moves()
+            .map(|unit_l| &*unit_l - unit)
+            .chain(unit.right_moves().map(|unit_r| unit - &*unit_r))
+            .map(|incentive| unit + incentive)
+            .collect::<Vec<_>>();
+
+        let mut left_moves = Vec::new();
+        for left_move in self.left_moves() {
+            let left_move = left_move.norton_product(unit);
+            left_moves.extend(incentives.iter().map(|i| &left_move + i));
+        }
+
+        let mut right_moves = Vec::new();
+        for right_move in self.right_moves() {
+            let right_move = right_move.norton_product(unit);
+            right_moves.extend(incentives.iter().map(|i| &right_move - i));
+        }
+
+        Self::new_from_moves(left_moves, right_moves)
+    }
+
+    /// Berlekamp's overheating `\int_s^t G`, equal to `G.s` if `G` is an integer, or
+    /// `{t + \int_s^t G^L | \int_s^t G^R - t}` otherwise
+    #[must_use]
+    pub fn overheat(&self, base: &Self, temperature: &Self) -> Self {
+        if let Some(integer) = self.to_number().and_then(|n| n.to_integer()) {
+            return base.integer_multiple(integer);
+        }
+
+        let left_moves = self
+            .left_moves()
+            .map(|left_move| left_move.overheat(base, temperature) + temperature)
+            .collect();
+        let right_moves = self
+            .right_moves()
+            .map(|right_move| right_move.overheat(base, temperature) - temperature)
+            .collect();
+        Self::new_from_moves(left_moves, right_moves)
+    }
+
     /// A remote star of game `g` is a nimber `*N` if no position of `g` including `g` has value `N*`
     #[must_use]
     #[allow(clippy::or_fun_call)]
@@ -1049,6 +1236,61 @@ impl CanonicalForm {
     }
 }
 
+/// Keep moves whose incentive is not strictly smaller than another incentive
+fn dominant_by_incentive<M>(moves: impl Iterator<Item = (M, CanonicalForm)>) -> Vec<M> {
+    let moves = moves.collect::<Vec<_>>();
+    let dominated = moves
+        .iter()
+        .map(|(_, incentive)| {
+            moves
+                .iter()
+                .any(|(_, other)| incentive.partial_cmp(other) == Some(Ordering::Less))
+        })
+        .collect::<Vec<_>>();
+    moves
+        .into_iter()
+        .zip(dominated)
+        .filter(|(_, dominated)| !dominated)
+        .map(|((m, _), _)| m)
+        .collect()
+}
+
+/// Keep moves that are best for the player at ambient `temperature`, i.e. with the largest right
+/// wall of their thermograph for Left, or the smallest left wall for Right
+pub(crate) fn orthodox_moves<M>(
+    moves: impl Iterator<Item = (M, Thermograph)>,
+    player: Player,
+    temperature: Rational,
+) -> Vec<M> {
+    let moves = moves
+        .map(|(m, thermograph)| {
+            let wall = match player {
+                Player::Left => thermograph.right_wall.value_at(temperature),
+                Player::Right => -thermograph.left_wall.value_at(temperature),
+            };
+            (m, wall)
+        })
+        .collect::<Vec<_>>();
+    let Some(best) = moves.iter().map(|(_, wall)| *wall).max() else {
+        return Vec::new();
+    };
+    moves
+        .into_iter()
+        .filter(|(_, wall)| *wall == best)
+        .map(|(m, _)| m)
+        .collect()
+}
+
+/// Check if the thermograph wall has slope zero just below `temperature`
+fn is_vertical_below(wall: &Trajectory, temperature: Rational) -> bool {
+    let idx = wall
+        .critical_points
+        .iter()
+        .take_while(|critical_point| **critical_point >= temperature)
+        .count();
+    wall.slopes[idx] == Rational::from(0)
+}
+
 impl PartialEq for CanonicalForm {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
@@ -1088,6 +1330,8 @@ impl_op_ex!(-|g: &CanonicalForm, h: &CanonicalForm| -> CanonicalForm {
 impl_op_ex!(-=|g: &mut CanonicalForm, h: &CanonicalForm| {
     *g = CanonicalForm::construct_sum(g, &CanonicalForm::construct_negative(h));
 });
+impl_op_ex!(*|g: &CanonicalForm, u: &CanonicalForm| -> CanonicalForm { g.norton_product(u) });
+impl_op_ex!(*=|g: &mut CanonicalForm, u: &CanonicalForm| { *g = g.norton_product(u) });
 
 impl Display for CanonicalForm {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -1100,6 +1344,49 @@ impl Display for CanonicalForm {
 
 impl_from_str_via_parser!(CanonicalForm);
 
+impl Encode for CanonicalForm {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match &self.inner {
+            CanonicalFormInner::Nus(nus) => {
+                0u8.encode(buf);
+                nus.number().encode(buf);
+                nus.up_multiple().encode(buf);
+                nus.nimber().encode(buf);
+            }
+            CanonicalFormInner::Moves(moves) => {
+                1u8.encode(buf);
+                for options in [&moves.left, &moves.right] {
+                    options.len().encode(buf);
+                    for option in options {
+                        CanonicalForm::encode(option, buf);
+                    }
+                }
+            }
+        }
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        match u8::decode(input)? {
+            0 => {
+                let number = DyadicRationalNumber::decode(input)?;
+                let up_multiple = i32::decode(input)?;
+                let nimber = Nimber::decode(input)?;
+                Some(Self::new_nus(Nus::new(number, up_multiple, nimber)))
+            }
+            1 => {
+                // Options were canonical when encoded so there is no need to simplify them again
+                let left = Vec::<CanonicalForm>::decode(input)?;
+                let right = Vec::<CanonicalForm>::decode(input)?;
+                Some(Self::construct_from_canonical_moves(Moves {
+                    left: TotalWrapper::from_inner_vec(left),
+                    right: TotalWrapper::from_inner_vec(right),
+                }))
+            }
+            _ => None,
+        }
+    }
+}
+
 impl Sum for CanonicalForm {
     fn sum<I: Iterator<Item = CanonicalForm>>(iter: I) -> CanonicalForm {
         iter.fold(CanonicalForm::new_integer(0), |acc, v| acc + v)
@@ -1339,7 +1626,17 @@ mod tests {
         assert_negative_eq!("0", "0");
         assert_negative_eq!("42", "-42");
         assert_negative_eq!("-42", "42");
-        assert_negative_eq!("{^|*}", "{v|*}");
+        assert_negative_eq!("{^|*}", "{*|v}");
+        assert_negative_eq!("{3/2|-3/2}", "{3/2|-3/2}");
+        assert_negative_eq!("{2|{1|0}}", "{{0|-1}|-2}");
+    }
+
+    #[test]
+    fn difference_with_itself_is_zero() {
+        for game in ["{^|*}", "{2|{1|0}}", "{3|{1|*}}", "{{2|1}, 1|-1}"] {
+            let game = cf!(game);
+            assert_eq!(&game - &game, CanonicalForm::new_integer(0), "{game}");
+        }
     }
 
     #[test]
@@ -1609,4 +1906,115 @@ mod tests {
         Moves::eliminate_dominated_moves(&mut moves, Player::Left);
         assert_eq_iter_str!(moves, [cf!("1"), cf!("{2|0}")]);
     }
+
+    #[test]
+    fn ordinal_sum_works() {
+        // Hackenbush stalks
+        assert_eq!(cf!("1").ordinal_sum(&cf!("1")), cf!("2"));
+        assert_eq!(cf!("1").ordinal_sum(&cf!("-1")), cf!("1/2"));
+        assert_eq!(cf!("1").ordinal_sum(&cf!("-2")), cf!("1/4"));
+        assert_eq!(
+            cf!("1").ordinal_sum(&cf!("-1")).ordinal_sum(&cf!("-1")),
+            cf!("1").ordinal_sum(&cf!("-1").ordinal_sum(&cf!("-1")))
+        );
+
+        assert_eq!(cf!("*").ordinal_sum(&cf!("*")), cf!("*2"));
+        assert_eq!(cf!("0").ordinal_sum(&cf!("{2|*}")), cf!("{2|*}"));
+        assert_eq!(cf!("{2|*}").ordinal_sum(&cf!("0")), cf!("{2|*}"));
+    }
+
+    #[test]
+    fn norton_product_works() {
+        let up = cf!("^");
+        assert_eq!(cf!("2") * &up, &up + &up);
+        assert_eq!(cf!("-3") * &up, -(&up + &up + &up));
+        assert_eq!((cf!("3/4") * cf!("1")), cf!("3/4"));
+
+        // Norton product is linear in the multiplier
+        let games = ["1/2", "3/4", "{1|0}", "{2|-1}", "*"];
+        for unit in ["^", "1", "^2*", "1/2", "{2|{1|0}}"] {
+            let unit = cf!(unit);
+            for lhs in games {
+                for rhs in games {
+                    assert_eq!(
+                        (cf!(lhs) + cf!(rhs)) * &unit,
+                        cf!(lhs) * &unit + cf!(rhs) * &unit
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn overheating_works() {
+        let one = cf!("1");
+        assert_eq!(cf!("3").overheat(&cf!("^"), &one), cf!("3") * cf!("^"));
+        assert_eq!(cf!("*").overheat(&one, &one), cf!("{1|-1}"));
+        assert_eq!(cf!("1/2").overheat(&one, &one), cf!("{1|0}"));
+
+        // Overheating non-numbers from the base 1 is the same as heating
+        for g in ["{1|-1}", "{2|*}", "^", "{3|{1|0}}"] {
+            assert_eq!(cf!(g).overheat(&one, &one), cf!(g).heat(&one));
+        }
+
+        // Temperature with asymmetric options is negated on Right moves
+        let temperature = cf!("{2|{1|0}}");
+        assert_eq!(
+            cf!("1/2").overheat(&one, &temperature),
+            cf!("{{2|{1|0}}|{{1|0}|-1}}")
+        );
+    }
+
+    #[test]
+    fn incentives() {
+        let g = cf!("{2|-1}");
+        assert_eq!(g.left_incentives(), vec![cf!("2") - &g]);
+        assert_eq!(g.right_incentives(), vec![&g - cf!("-1")]);
+
+        // Incentive recovers the game from the option
+        let g = cf!("{2|{1|0}}");
+        assert_eq!(g.left_incentives(), vec![cf!("{{2|1}|0}")]);
+        let [right_incentive] = g.right_incentives().try_into().unwrap();
+        assert_eq!(cf!("{1|0}") + right_incentive, g);
+
+        // Move in the hotter component is dominant
+        let components = [cf!("{2|-2}"), cf!("{1|-1}"), cf!("*")];
+        assert_eq!(
+            CanonicalForm::dominant_left_moves(&components),
+            vec![(0, cf!("2"))]
+        );
+        assert_eq!(
+            CanonicalForm::dominant_right_moves(&components),
+            vec![(0, cf!("-2"))]
+        );
+
+        // Equal incentives are both kept
+        let components = [cf!("{1|-1}"), cf!("{1|-1}")];
+        assert_eq!(CanonicalForm::dominant_left_moves(&components).len(), 2);
+    }
+
+    #[test]
+    fn orthodox_moves() {
+        let g = cf!("{{4|0}, 1|-1}");
+        let zero = DyadicRationalNumber::from(0);
+        // Threat is only worth it when the ambient temperature is high
+        assert_eq!(g.orthodox_left_moves(zero), vec![cf!("1")]);
+        assert_eq!(
+            g.orthodox_left_moves(DyadicRationalNumber::from(3)),
+            vec![cf!("{4|0}")]
+        );
+        assert_eq!(g.orthodox_right_moves(zero), vec![cf!("-1")]);
+        assert!(cf!("1").orthodox_left_moves(zero).len() == 1);
+        assert!(cf!("-1").orthodox_left_moves(zero).is_empty());
+    }
+
+    #[test]
+    fn tempo() {
+        assert_eq!(cf!("{1|-1}").tempo(), Some(Tempo::Gote));
+        assert_eq!(cf!("{{4|0}|-1}").tempo(), Some(Tempo::LeftSente));
+        assert_eq!(cf!("{1|{0|-4}}").tempo(), Some(Tempo::RightSente));
+        assert_eq!(cf!("^").tempo(), Some(Tempo::Tedomari));
+        assert_eq!(cf!("*").tempo(), Some(Tempo::Tedomari));
+        assert_eq!(cf!("3/4").tempo(), None);
+    }
 }
@@ -0,0 +1,454 @@
+//! Combinators building new positions from existing ones, possibly of different game types
+
+use crate::{
+    numeric::dyadic_rational_number::DyadicRationalNumber,
+    short::{
+        impartial::impartial_game::ImpartialGame,
+        partizan::{
+            canonical_form::CanonicalForm, partizan_game::PartizanGame,
+            transposition_table::ParallelTranspositionTable,
+        },
+    },
+};
+use std::fmt::{self, Display};
+
+/// Disjunctive sum of two positions of possibly different games. Player moves in exactly one of
+/// the components.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sum<A, B> {
+    // Missing component is equal to zero, so the sum can be split into components of type `Self`
+    first: Option<A>,
+    second: Option<B>,
+}
+
+impl<A, B> Sum<A, B> {
+    /// Construct sum of two positions
+    #[inline]
+    pub const fn new(first: A, second: B) -> Self {
+        Self {
+            first: Some(first),
+            second: Some(second),
+        }
+    }
+
+    /// Get the first component, `None` if it was split off by [`PartizanGame::decompositions`]
+    #[inline]
+    pub const fn first(&self) -> Option<&A> {
+        self.first.as_ref()
+    }
+
+    /// Get the second component, `None` if it was split off by [`PartizanGame::decompositions`]
+    #[inline]
+    pub const fn second(&self) -> Option<&B> {
+        self.second.as_ref()
+    }
+
+    fn moves(
+        &self,
+        first_moves: impl FnOnce(&A) -> Vec<A>,
+        second_moves: impl FnOnce(&B) -> Vec<B>,
+    ) -> Vec<Self>
+    where
+        A: Clone,
+        B: Clone,
+    {
+        let mut moves = Vec::new();
+        if let Some(first) = &self.first {
+            moves.extend(first_moves(first).into_iter().map(|first| Self {
+                first: Some(first),
+                second: self.second.clone(),
+            }));
+        }
+        if let Some(second) = &self.second {
+            moves.extend(second_moves(second).into_iter().map(|second| Self {
+                first: self.first.clone(),
+                second: Some(second),
+            }));
+        }
+        moves
+    }
+}
+
+impl<A, B> Display for Sum<A, B>
+where
+    A: Display,
+    B: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.first, &self.second) {
+            (Some(first), Some(second)) => write!(f, "{first} + {second}"),
+            (Some(first), None) => write!(f, "{first}"),
+            (None, Some(second)) => write!(f, "{second}"),
+            (None, None) => write!(f, "0"),
+        }
+    }
+}
+
+impl<A, B> PartizanGame for Sum<A, B>
+where
+    A: PartizanGame,
+    B: PartizanGame,
+{
+    fn left_moves(&self) -> Vec<Self> {
+        self.moves(A::left_moves, B::left_moves)
+    }
+
+    fn right_moves(&self) -> Vec<Self> {
+        self.moves(A::right_moves, B::right_moves)
+    }
+
+    fn decompositions(&self) -> Vec<Self> {
+        let first = self
+            .first
+            .iter()
+            .flat_map(A::decompositions)
+            .map(|first| Self {
+                first: Some(first),
+                second: None,
+            });
+        let second = self
+            .second
+            .iter()
+            .flat_map(B::decompositions)
+            .map(|second| Self {
+                first: None,
+                second: Some(second),
+            });
+        first.chain(second).collect()
+    }
+}
+
+/// Disjunctive sum of any number of positions of the same game
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SumVec<G> {
+    components: Vec<G>,
+}
+
+impl<G> SumVec<G> {
+    /// Construct sum of positions
+    #[inline]
+    pub const fn new(components: Vec<G>) -> Self {
+        Self { components }
+    }
+
+    /// Get the components of the sum
+    #[inline]
+    pub fn components(&self) -> &[G] {
+        &self.components
+    }
+
+    fn moves(&self, component_moves: impl Fn(&G) -> Vec<G>) -> Vec<Self>
+    where
+        G: Clone,
+    {
+        let mut moves = Vec::new();
+        for (idx, component) in self.components.iter().enumerate() {
+            for component_move in component_moves(component) {
+                let mut components = self.components.clone();
+                components[idx] = component_move;
+                moves.push(Self { components });
+            }
+        }
+        moves
+    }
+}
+
+impl<G> Display for SumVec<G>
+where
+    G: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.components.is_empty() {
+            return write!(f, "0");
+        }
+        for (idx, component) in self.components.iter().enumerate() {
+            if idx != 0 {
+                write!(f, " + ")?;
+            }
+            write!(f, "{component}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<G> PartizanGame for SumVec<G>
+where
+    G: PartizanGame,
+{
+    fn left_moves(&self) -> Vec<Self> {
+        self.moves(G::left_moves)
+    }
+
+    fn right_moves(&self) -> Vec<Self> {
+        self.moves(G::right_moves)
+    }
+
+    fn decompositions(&self) -> Vec<Self> {
+        self.components
+            .iter()
+            .flat_map(G::decompositions)
+            .map(|component| Self::new(vec![component]))
+            .collect()
+    }
+}
+
+/// Negative of a position, i.e. the same position with roles of the players swapped
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Negative<G> {
+    position: G,
+}
+
+/// Conjugate of a position. Under normal play it is the same as [`Negative`].
+pub type Conjugate<G> = Negative<G>;
+
+impl<G> Negative<G> {
+    /// Construct negative of a position
+    #[inline]
+    pub const fn new(position: G) -> Self {
+        Self { position }
+    }
+
+    /// Get the negated position
+    #[inline]
+    pub const fn position(&self) -> &G {
+        &self.position
+    }
+
+    /// Get the negated position
+    #[inline]
+    pub fn into_inner(self) -> G {
+        self.position
+    }
+}
+
+impl<G> Display for Negative<G>
+where
+    G: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "-({})", self.position)
+    }
+}
+
+impl<G> PartizanGame for Negative<G>
+where
+    G: PartizanGame,
+{
+    fn left_moves(&self) -> Vec<Self> {
+        self.position
+            .right_moves()
+            .into_iter()
+            .map(Self::new)
+            .collect()
+    }
+
+    fn right_moves(&self) -> Vec<Self> {
+        self.position
+            .left_moves()
+            .into_iter()
+            .map(Self::new)
+            .collect()
+    }
+
+    fn decompositions(&self) -> Vec<Self> {
+        self.position
+            .decompositions()
+            .into_iter()
+            .map(Self::new)
+            .collect()
+    }
+}
+
+/// Impartial position viewed as a partizan game, where both players have the same moves
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Impartial<G> {
+    position: G,
+}
+
+impl<G> Impartial<G> {
+    /// Wrap impartial position
+    #[inline]
+    pub const fn new(position: G) -> Self {
+        Self { position }
+    }
+
+    /// Get the impartial position
+    #[inline]
+    pub const fn position(&self) -> &G {
+        &self.position
+    }
+
+    /// Get the impartial position
+    #[inline]
+    pub fn into_inner(self) -> G {
+        self.position
+    }
+}
+
+impl<G> Display for Impartial<G>
+where
+    G: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.position)
+    }
+}
+
+impl<G> PartizanGame for Impartial<G>
+where
+    G: ImpartialGame,
+{
+    fn left_moves(&self) -> Vec<Self> {
+        self.position.moves().into_iter().map(Self::new).collect()
+    }
+
+    fn right_moves(&self) -> Vec<Self> {
+        self.left_moves()
+    }
+
+    fn decompositions(&self) -> Vec<Self> {
+        self.position
+            .decompositions()
+            .into_iter()
+            .map(Self::new)
+            .collect()
+    }
+
+    fn reductions(&self) -> Option<CanonicalForm> {
+        // Value of every impartial game is a nimber
+        Some(CanonicalForm::new_nimber(
+            DyadicRationalNumber::from(0),
+            self.position.nim_value(&ParallelTranspositionTable::new()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::{
+        canonical_form::CanonicalForm,
+        games::{domineering::Domineering, toads_and_frogs::ToadsAndFrogs},
+        transposition_table::{NoTranspositionTable, ParallelTranspositionTable},
+    };
+    use crate::{
+        numeric::nimber::Nimber,
+        short::impartial::{
+            games::{quicksort::Quicksort, subtraction::Sub},
+            heap_game::HeapPosition,
+        },
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn mixed_sum() {
+        let domineering: Domineering = Domineering::from_str("...|..#|...").unwrap();
+        let toads_and_frogs = ToadsAndFrogs::from_str("T..F.").unwrap();
+        let expected = domineering.canonical_form(&ParallelTranspositionTable::new())
+            + toads_and_frogs.canonical_form(&ParallelTranspositionTable::new());
+
+        let sum = Sum::new(domineering, toads_and_frogs.clone());
+        assert_eq!(sum.to_string(), "...|..#|... + T..F.");
+        assert_eq!(
+            sum.canonical_form(&ParallelTranspositionTable::new()),
+            expected
+        );
+        assert_eq!(
+            sum.thermograph_direct(),
+            expected.thermograph(),
+            "{}",
+            expected
+        );
+        assert_eq!(sum.outcome(), expected.outcome());
+
+        assert_eq!(sum.decompositions().len(), 2);
+        // Domineering position splits into two components
+        let domineering: Domineering = Domineering::from_str("..#|###|#..").unwrap();
+        let split = Sum::new(domineering, toads_and_frogs.clone());
+        assert_eq!(split.decompositions().len(), 3);
+
+        let negative = Negative::new(sum);
+        assert_eq!(
+            negative.canonical_form(&ParallelTranspositionTable::new()),
+            -&expected
+        );
+
+        let zero = Sum::new(toads_and_frogs.clone(), Conjugate::new(toads_and_frogs));
+        assert_eq!(
+            zero.canonical_form(&ParallelTranspositionTable::new()),
+            CanonicalForm::new_integer(0)
+        );
+    }
+
+    #[test]
+    fn vector_sum() {
+        let components = ["..|..", "...", ".#.|..."]
+            .map(|position| Domineering::from_str(position).unwrap())
+            .to_vec();
+        let tt = ParallelTranspositionTable::new();
+        let expected = components
+            .iter()
+            .map(|component| component.canonical_form(&tt))
+            .sum::<CanonicalForm>();
+
+        let sum: SumVec<Domineering> = SumVec::new(components);
+        assert_eq!(sum.to_string(), "..|.. + ... + .#.|...");
+        assert_eq!(
+            sum.canonical_form(&ParallelTranspositionTable::new()),
+            expected
+        );
+        assert_eq!(SumVec::<Domineering>::new(vec![]).to_string(), "0");
+    }
+
+    #[test]
+    fn impartial_positions() {
+        let quicksort = Impartial::new(Quicksort::new(vec![2, 3, 1, 5, 4]));
+        let expected = CanonicalForm::new_nimber(
+            DyadicRationalNumber::from(0),
+            quicksort
+                .position()
+                .nim_value(&ParallelTranspositionTable::new()),
+        );
+        assert_eq!(
+            quicksort.canonical_form(&ParallelTranspositionTable::new()),
+            expected
+        );
+        // Without the nimber shortcut the value is the same
+        assert_eq!(
+            CanonicalForm::new_from_moves(
+                quicksort
+                    .left_moves()
+                    .iter()
+                    .map(|m| m.canonical_form(&NoTranspositionTable::new()))
+                    .collect(),
+                quicksort
+                    .right_moves()
+                    .iter()
+                    .map(|m| m.canonical_form(&NoTranspositionTable::new()))
+                    .collect(),
+            ),
+            expected
+        );
+
+        // Subtraction game with heap of size 7 is *3, and Quicksort[2, 1] is *1
+        let sub = HeapPosition::new(Sub::new(vec![1, 2, 3]), vec![7]);
+        assert_eq!(
+            sub.nim_value(&ParallelTranspositionTable::new()),
+            Nimber::new(3)
+        );
+        assert_eq!(sub.to_string(), "Sub(1, 2, 3)[7]");
+        let sum = Sum::new(
+            Impartial::new(sub),
+            Impartial::new(Quicksort::new(vec![2, 1])),
+        );
+        assert_eq!(
+            sum.canonical_form(&ParallelTranspositionTable::new())
+                .to_string(),
+            "*2"
+        );
+    }
+}
@@ -0,0 +1,442 @@
+//! Game tree of a position, with transpositions collapsed into a DAG. Can be exported to
+//! [graphviz](https://graphviz.org/) or JSON.
+
+use crate::{
+    short::partizan::{
+        Player, canonical_form::CanonicalForm, partizan_game::PartizanGame,
+        transposition_table::TranspositionTable,
+    },
+    total::TotalWrapper,
+};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, VecDeque},
+    fmt::{Display, Write},
+    hash::Hash,
+};
+
+/// Kind of an option in the game tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OptionKind {
+    /// Option that is present in the canonical form
+    Canonical,
+
+    /// Option that is dominated by another option of the same player
+    Dominated,
+
+    /// Option that is reversible through an option of the opponent
+    Reversible,
+}
+
+impl OptionKind {
+    const fn as_str(self) -> &'static str {
+        match self {
+            OptionKind::Canonical => "canonical",
+            OptionKind::Dominated => "dominated",
+            OptionKind::Reversible => "reversible",
+        }
+    }
+}
+
+/// Edge of the game tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Edge {
+    /// Index of the node after the move
+    pub target: usize,
+
+    /// Kind of the option
+    pub kind: OptionKind,
+}
+
+/// Node of the game tree
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Node {
+    /// Position in the node
+    pub label: String,
+
+    /// Value of the position
+    pub value: CanonicalForm,
+
+    /// Moves of the Left player
+    pub left: Vec<Edge>,
+
+    /// Moves of the Right player
+    pub right: Vec<Edge>,
+
+    /// Node has moves that were not expanded because of the depth limit
+    pub truncated: bool,
+}
+
+/// Game tree, where identical positions share a single node
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameTree {
+    nodes: Vec<Node>,
+}
+
+impl GameTree {
+    /// Construct game tree of a canonical form up to given depth. All options of canonical
+    /// forms are canonical.
+    pub fn from_canonical_form(game: &CanonicalForm, depth: usize) -> Self {
+        Self::build(
+            TotalWrapper::new(game.clone()),
+            depth,
+            |g| g.to_string(),
+            |g| (**g).clone(),
+            |g, player| match player {
+                Player::Left => g.left_moves().map(wrap).collect(),
+                Player::Right => g.right_moves().map(wrap).collect(),
+            },
+        )
+    }
+
+    /// Construct game tree of a position up to given depth, marking dominated and reversible
+    /// options
+    pub fn from_position<G, TT>(position: &G, depth: usize, transposition_table: &TT) -> Self
+    where
+        G: PartizanGame + Display,
+        TT: TranspositionTable<G> + Sync,
+    {
+        Self::build(
+            position.clone(),
+            depth,
+            ToString::to_string,
+            |g| g.canonical_form(transposition_table),
+            |g, player| match player {
+                Player::Left => g.left_moves(),
+                Player::Right => g.right_moves(),
+            },
+        )
+    }
+
+    fn build<G>(
+        root: G,
+        depth: usize,
+        label: impl Fn(&G) -> String,
+        value: impl Fn(&G) -> CanonicalForm,
+        moves: impl Fn(&G, Player) -> Vec<G>,
+    ) -> Self
+    where
+        G: Clone + Hash + Eq,
+    {
+        let mut indices = HashMap::new();
+        let mut nodes = Vec::new();
+        let mut positions = Vec::new();
+        let mut queue = VecDeque::new();
+
+        let mut add_node = |position: G,
+                            nodes: &mut Vec<Node>,
+                            positions: &mut Vec<G>,
+                            queue: &mut VecDeque<_>,
+                            d| {
+            *indices.entry(position.clone()).or_insert_with(|| {
+                nodes.push(Node {
+                    label: label(&position),
+                    value: value(&position),
+                    left: Vec::new(),
+                    right: Vec::new(),
+                    truncated: false,
+                });
+                queue.push_back((nodes.len() - 1, d));
+                positions.push(position);
+                nodes.len() - 1
+            })
+        };
+
+        add_node(root, &mut nodes, &mut positions, &mut queue, 0);
+        // Breadth first, so every position is expanded at the smallest depth it appears
+        while let Some((idx, d)) = queue.pop_front() {
+            let position = positions[idx].clone();
+            let left_moves = moves(&position, Player::Left);
+            let right_moves = moves(&position, Player::Right);
+            if d >= depth {
+                nodes[idx].truncated = !left_moves.is_empty() || !right_moves.is_empty();
+                continue;
+            }
+
+            for (player, options) in [(Player::Left, left_moves), (Player::Right, right_moves)] {
+                let targets = options
+                    .into_iter()
+                    .map(|option| add_node(option, &mut nodes, &mut positions, &mut queue, d + 1))
+                    .collect::<Vec<_>>();
+                let edges = targets
+                    .iter()
+                    .enumerate()
+                    .map(|(option_idx, &target)| Edge {
+                        target,
+                        kind: option_kind(&nodes, idx, &targets, option_idx, player),
+                    })
+                    .collect::<Vec<_>>();
+                match player {
+                    Player::Left => nodes[idx].left = edges,
+                    Player::Right => nodes[idx].right = edges,
+                }
+            }
+        }
+
+        Self { nodes }
+    }
+
+    /// Get all nodes in the tree. Root is the first node.
+    #[inline]
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Render to a [graphviz](https://graphviz.org/) format, that can be later rendered to an
+    /// image with external engine. Left moves are blue, right moves are red, dominated options
+    /// are dashed and reversible options are dotted.
+    pub fn to_graphviz(&self) -> String {
+        let mut buf = String::new();
+
+        write!(buf, "digraph G {{").unwrap();
+        for (idx, node) in self.nodes.iter().enumerate() {
+            let label = if node.label == node.value.to_string() {
+                node.label.clone()
+            } else {
+                format!("{}\n{}", node.label, node.value)
+            };
+            let shape = if node.truncated { "box" } else { "ellipse" };
+            write!(
+                buf,
+                "{} [label=\"{}\", shape={}];",
+                idx,
+                escape_graphviz(&label),
+                shape
+            )
+            .unwrap();
+        }
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            for (color, edges) in [("blue", &node.left), ("red", &node.right)] {
+                for edge in edges {
+                    let style = match edge.kind {
+                        OptionKind::Canonical => "solid",
+                        OptionKind::Dominated => "dashed",
+                        OptionKind::Reversible => "dotted",
+                    };
+                    write!(
+                        buf,
+                        "{} -> {} [color={}, style={}];",
+                        idx, edge.target, color, style
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        write!(buf, "}}").unwrap();
+
+        buf
+    }
+
+    /// Render to JSON, with nodes identified by their index in [`GameTree::nodes`]
+    pub fn to_json(&self) -> String {
+        let edges = |edges: &[Edge]| {
+            edges
+                .iter()
+                .map(|edge| {
+                    format!(
+                        "{{\"target\":{},\"kind\":\"{}\"}}",
+                        edge.target,
+                        edge.kind.as_str()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        let nodes = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, node)| {
+                format!(
+                    "{{\"id\":{},\"label\":\"{}\",\"value\":\"{}\",\"truncated\":{},\"left\":[{}],\"right\":[{}]}}",
+                    idx,
+                    escape_json(&node.label),
+                    escape_json(&node.value.to_string()),
+                    node.truncated,
+                    edges(&node.left),
+                    edges(&node.right),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!("{{\"root\":0,\"nodes\":[{}]}}", nodes)
+    }
+}
+
+fn wrap(game: Cow<'_, CanonicalForm>) -> TotalWrapper<CanonicalForm> {
+    TotalWrapper::new(game.into_owned())
+}
+
+/// Classify option by comparing values of options of the same player
+fn option_kind(
+    nodes: &[Node],
+    parent: usize,
+    options: &[usize],
+    option_idx: usize,
+    player: Player,
+) -> OptionKind {
+    let value = &nodes[options[option_idx]].value;
+    let dominated = options.iter().enumerate().any(|(other_idx, &other)| {
+        let other = &nodes[other].value;
+        let better = match player {
+            Player::Left => value < other,
+            Player::Right => value > other,
+        };
+        // Only the first of equal options is kept
+        better || (other_idx < option_idx && value == other)
+    });
+    if dominated {
+        return OptionKind::Dominated;
+    }
+
+    let parent = &nodes[parent].value;
+    let reversible = match player {
+        Player::Left => value.right_moves().any(|reverse| &*reverse <= parent),
+        Player::Right => value.left_moves().any(|reverse| &*reverse >= parent),
+    };
+    if reversible {
+        OptionKind::Reversible
+    } else {
+        OptionKind::Canonical
+    }
+}
+
+/// Escape string for Graphviz label, where `\n` is a line break
+fn escape_graphviz(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape string for JSON string literal
+fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                write!(escaped, "\\u{:04x}", c as u32).unwrap();
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::{
+        games::domineering::Domineering, transposition_table::ParallelTranspositionTable,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn canonical_form_tree() {
+        let game = CanonicalForm::from_str("{1|{0|-1}}").unwrap();
+        let tree = GameTree::from_canonical_form(&game, 10);
+        let labels = tree
+            .nodes()
+            .iter()
+            .map(|node| node.label.as_str())
+            .collect::<Vec<_>>();
+        // Both 0 and -1 are shared between different parents
+        assert_eq!(labels, ["{1|{0|-1}}", "1", "{0|-1}", "0", "-1"]);
+        assert!(
+            tree.nodes()
+                .iter()
+                .flat_map(|node| node.left.iter().chain(&node.right))
+                .all(|edge| edge.kind == OptionKind::Canonical)
+        );
+
+        let tree = GameTree::from_canonical_form(&game, 1);
+        assert_eq!(tree.nodes().len(), 3);
+        assert!(tree.nodes()[1].truncated && tree.nodes()[2].truncated);
+        assert_eq!(
+            tree.to_graphviz(),
+            "digraph G {0 [label=\"{1|{0|-1}}\", shape=ellipse];1 [label=\"1\", shape=box];2 [label=\"{0|-1}\", shape=box];0 -> 1 [color=blue, style=solid];0 -> 2 [color=red, style=solid];}"
+        );
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Tree(Vec<Tree>, Vec<Tree>);
+
+    impl PartizanGame for Tree {
+        fn left_moves(&self) -> Vec<Self> {
+            self.0.clone()
+        }
+
+        fn right_moves(&self) -> Vec<Self> {
+            self.1.clone()
+        }
+    }
+
+    impl Display for Tree {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            crate::display::braces(f, |f| {
+                crate::display::commas(f, &self.0)?;
+                write!(f, "|")?;
+                crate::display::commas(f, &self.1)
+            })
+        }
+    }
+
+    #[test]
+    fn reversible_options() {
+        // {{2|0}|} = 0 because {2|0} reverses through 0
+        let zero = Tree(vec![], vec![]);
+        let two = Tree(vec![Tree(vec![zero.clone()], vec![])], vec![]);
+        let game = Tree(vec![Tree(vec![two], vec![zero])], vec![]);
+        let tree = GameTree::from_position(&game, 1, &ParallelTranspositionTable::new());
+        assert_eq!(tree.nodes()[0].value.to_string(), "0");
+        assert_eq!(tree.nodes()[0].left[0].kind, OptionKind::Reversible);
+    }
+
+    #[test]
+    fn position_tree() {
+        let position = Domineering::from_str("...|...").unwrap();
+        let tree = GameTree::from_position::<Domineering, _>(
+            &position,
+            1,
+            &ParallelTranspositionTable::new(),
+        );
+        let root = &tree.nodes()[0];
+        assert_eq!(root.value.to_string(), "{2|-1/2}");
+        // Vertical dominoes at the edges leave the same position
+        assert_eq!(root.left.len(), 2);
+        assert_eq!(root.left[0].kind, OptionKind::Dominated);
+        assert_eq!(root.left[1].kind, OptionKind::Canonical);
+        assert_eq!(tree.nodes()[root.left[0].target].label, "..|..");
+        assert_eq!(root.right.len(), 4);
+
+        let json = tree.to_json();
+        assert!(json.starts_with("{\"root\":0,\"nodes\":[{\"id\":0,\"label\":\"...|...\""));
+    }
+
+    #[test]
+    fn json_escapes_control_characters() {
+        assert_eq!(
+            escape_json("a\"b\\c\nd\te\u{1}f\u{1f}"),
+            "a\\\"b\\\\c\\nd\\te\\u0001f\\u001f"
+        );
+        assert_eq!(escape_graphviz("a\"b\nc"), "a\\\"b\\nc");
+    }
+}
@@ -1,10 +1,23 @@
 //! Partizan games under normal play i.e. the player that cannot move in their turn loses.
 
 pub mod amazons;
+pub mod cherries;
+pub mod clobber;
+pub mod col;
+pub mod cutcake;
 pub mod digraph_placement;
 pub mod domineering;
+pub mod eatcake;
 pub mod fission;
+pub mod go_endgame;
+pub mod hackenbush;
 pub mod konane;
+pub mod maundy_cake;
+pub mod node_coloring;
+pub mod partizan_quicksort;
+pub mod partizan_subtraction;
+pub mod push;
+pub mod shove;
 pub mod ski_jumps;
 pub mod snort;
 pub mod toads_and_frogs;
@@ -2,8 +2,9 @@
 
 use crate::{
     drawing::{self, BoundingBox, Canvas, Color, Draw},
-    grid::{FiniteGrid, Grid, decompositions, move_top_left, vec_grid::VecGrid},
-    short::partizan::{Player, partizan_game::PartizanGame},
+    encoding::Encode,
+    grid::{FiniteGrid, Grid, Symmetry, decompositions, move_top_left, vec_grid::VecGrid},
+    short::partizan::{Player, partizan_game::PartizanGame, symmetry::SymmetricPosition},
 };
 use cgt_derive::Tile;
 use std::{fmt::Display, hash::Hash, str::FromStr};
@@ -52,6 +53,34 @@ pub struct Amazons<G = VecGrid<Tile>> {
     grid: G,
 }
 
+impl<G> SymmetricPosition for Amazons<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid,
+{
+    fn transform(&self, symmetry: Symmetry) -> Self {
+        Self::new(symmetry.apply(&self.grid))
+    }
+
+    fn swaps_players(_symmetry: Symmetry) -> bool {
+        // Queens move and shoot along lines in all eight directions, so no symmetry restricts a
+        // player
+        false
+    }
+}
+
+impl<G> Encode for Amazons<G>
+where
+    G: Encode,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.grid.encode(buf);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        G::decode(input).map(|grid| Self { grid })
+    }
+}
+
 impl<G> Display for Amazons<G>
 where
     G: Grid<Item = Tile> + FiniteGrid,
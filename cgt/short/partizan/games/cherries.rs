@@ -0,0 +1,218 @@
+//! Cherries is played on a grid of blue and red cherries. In each turn player picks one of their
+//! cherries that is at an end of a line, i.e. is orthogonally adjacent to at most one other
+//! cherry.
+//!
+//! On a single row this is the original game, where cherries are picked from either end of a row.
+
+use crate::{
+    drawing::{BoundingBox, Canvas, Draw},
+    encoding::Encode,
+    grid::{FiniteGrid, Grid, Symmetry, decompositions, vec_grid::VecGrid},
+    short::partizan::{
+        Player, partizan_game::PartizanGame, stone::Stone, symmetry::SymmetricPosition,
+    },
+};
+use std::{fmt::Display, hash::Hash, str::FromStr};
+
+const DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// Game of Cherries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cherries<G = VecGrid<Stone>> {
+    grid: G,
+}
+
+impl<G> Cherries<G>
+where
+    G: Grid<Item = Stone> + FiniteGrid,
+{
+    /// Create new Cherries game from a grid
+    #[inline]
+    pub const fn new(grid: G) -> Self {
+        Self { grid }
+    }
+
+    /// Get underlying grid
+    #[inline]
+    pub const fn grid(&self) -> &G {
+        &self.grid
+    }
+
+    /// Get underlying grid mutably
+    #[inline]
+    pub const fn grid_mut(&mut self) -> &mut G {
+        &mut self.grid
+    }
+
+    fn moves_for(&self, player: Player) -> Vec<Self>
+    where
+        G: Clone,
+    {
+        let own = Stone::from(player);
+
+        let mut moves = Vec::new();
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                if self.grid.get(x, y) == own && self.adjacent_cherries(x, y) <= 1 {
+                    let mut position = self.clone();
+                    position.grid.set(x, y, Stone::Empty);
+                    moves.push(position);
+                }
+            }
+        }
+        moves
+    }
+
+    fn adjacent_cherries(&self, x: u8, y: u8) -> usize {
+        DIRECTIONS
+            .iter()
+            .filter(|(dx, dy)| {
+                let (Some(ax), Some(ay)) = (
+                    x.checked_add_signed(*dx as i8),
+                    y.checked_add_signed(*dy as i8),
+                ) else {
+                    return false;
+                };
+                ax < self.grid.width()
+                    && ay < self.grid.height()
+                    && self.grid.get(ax, ay).is_occupied()
+            })
+            .count()
+    }
+}
+
+impl<G> Draw for Cherries<G>
+where
+    G: Grid<Item = Stone> + FiniteGrid,
+{
+    fn draw<C>(&self, canvas: &mut C)
+    where
+        C: Canvas,
+    {
+        self.grid.draw(canvas, Stone::drawing_tile);
+    }
+
+    fn required_canvas<C>(&self) -> BoundingBox
+    where
+        C: Canvas,
+    {
+        self.grid().canvas_size::<C>()
+    }
+}
+
+impl<G> PartizanGame for Cherries<G>
+where
+    G: Grid<Item = Stone> + FiniteGrid + Clone + Send + Sync + Eq + Hash,
+{
+    fn left_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Left)
+    }
+
+    fn right_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Right)
+    }
+
+    /// Picking a cherry changes only what can be picked among its orthogonal neighbours
+    fn decompositions(&self) -> Vec<Self> {
+        decompositions(&self.grid, Stone::is_occupied, Stone::Empty, &DIRECTIONS)
+            .into_iter()
+            .map(Self::new)
+            .collect::<Vec<_>>()
+    }
+}
+
+impl<G> SymmetricPosition for Cherries<G>
+where
+    G: Grid<Item = Stone> + FiniteGrid,
+{
+    fn transform(&self, symmetry: Symmetry) -> Self {
+        Self::new(symmetry.apply(&self.grid))
+    }
+
+    fn swaps_players(_symmetry: Symmetry) -> bool {
+        // Rules do not depend on direction, so the value does not change
+        false
+    }
+}
+
+impl<G> Encode for Cherries<G>
+where
+    G: Encode,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.grid.encode(buf);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        G::decode(input).map(|grid| Self { grid })
+    }
+}
+
+impl<G> Display for Cherries<G>
+where
+    G: Grid<Item = Stone> + FiniteGrid,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.grid.display(f, '|')
+    }
+}
+
+impl<G> FromStr for Cherries<G>
+where
+    G: Grid<Item = Stone> + FiniteGrid,
+{
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(G::parse(s).ok_or(())?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::transposition_table::ParallelTranspositionTable;
+
+    #[test]
+    fn moves() {
+        let position: Cherries = Cherries::from_str("xox|x..").unwrap();
+        assert_eq!(
+            position.left_moves(),
+            vec![
+                Cherries::from_str("xo.|x..").unwrap(),
+                Cherries::from_str("xox|...").unwrap(),
+            ]
+        );
+        assert_eq!(position.right_moves(), vec![]);
+
+        let position: Cherries = Cherries::from_str("xo.x|...o").unwrap();
+        assert_eq!(
+            position.decompositions(),
+            vec![
+                Cherries::from_str("xo").unwrap(),
+                Cherries::from_str("x|o").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn values() {
+        let tt = ParallelTranspositionTable::new();
+        for (position, value) in [
+            ("xx", "2"),
+            ("xo", "0"),
+            ("xox", "1"),
+            ("xxo", "1"),
+            ("x.o", "0"),
+            ("xo|ox", "0"),
+        ] {
+            let position: Cherries = Cherries::from_str(position).unwrap();
+            assert_eq!(
+                position.canonical_form(&tt).to_string(),
+                value,
+                "{position}"
+            );
+        }
+    }
+}
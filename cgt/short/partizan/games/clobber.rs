@@ -0,0 +1,222 @@
+//! Clobber is played on a grid of blue and red stones. In each turn player moves one of their
+//! stones onto orthogonally adjacent stone of the opponent, which is removed from the board.
+//!
+//! Linear Clobber is played on a single row, i.e. `1×n` grid.
+
+use crate::{
+    drawing::{BoundingBox, Canvas, Draw},
+    encoding::Encode,
+    grid::{FiniteGrid, Grid, Symmetry, decompositions, vec_grid::VecGrid},
+    short::partizan::{
+        Player, partizan_game::PartizanGame, stone::Stone, symmetry::SymmetricPosition,
+    },
+};
+use std::{fmt::Display, hash::Hash, str::FromStr};
+
+const DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// Game of Clobber
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Clobber<G = VecGrid<Stone>> {
+    grid: G,
+}
+
+impl<G> Clobber<G>
+where
+    G: Grid<Item = Stone> + FiniteGrid,
+{
+    /// Create new Clobber game from a grid
+    #[inline]
+    pub const fn new(grid: G) -> Self {
+        Self { grid }
+    }
+
+    /// Get underlying grid
+    #[inline]
+    pub const fn grid(&self) -> &G {
+        &self.grid
+    }
+
+    /// Get underlying grid mutably
+    #[inline]
+    pub const fn grid_mut(&mut self) -> &mut G {
+        &mut self.grid
+    }
+
+    fn moves_for(&self, player: Player) -> Vec<Self>
+    where
+        G: Clone,
+    {
+        let own = Stone::from(player);
+        let opponent = Stone::from(player.opposite());
+
+        let mut moves = Vec::new();
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                if self.grid.get(x, y) != own {
+                    continue;
+                }
+                for (dx, dy) in DIRECTIONS {
+                    let (Some(tx), Some(ty)) = (
+                        x.checked_add_signed(dx as i8),
+                        y.checked_add_signed(dy as i8),
+                    ) else {
+                        continue;
+                    };
+                    if tx < self.grid.width()
+                        && ty < self.grid.height()
+                        && self.grid.get(tx, ty) == opponent
+                    {
+                        let mut position = self.clone();
+                        position.grid.set(x, y, Stone::Empty);
+                        position.grid.set(tx, ty, own);
+                        moves.push(position);
+                    }
+                }
+            }
+        }
+        moves
+    }
+}
+
+impl<G> Draw for Clobber<G>
+where
+    G: Grid<Item = Stone> + FiniteGrid,
+{
+    fn draw<C>(&self, canvas: &mut C)
+    where
+        C: Canvas,
+    {
+        self.grid.draw(canvas, Stone::drawing_tile);
+    }
+
+    fn required_canvas<C>(&self) -> BoundingBox
+    where
+        C: Canvas,
+    {
+        self.grid().canvas_size::<C>()
+    }
+}
+
+impl<G> PartizanGame for Clobber<G>
+where
+    G: Grid<Item = Stone> + FiniteGrid + Clone + Send + Sync + Eq + Hash,
+{
+    fn left_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Left)
+    }
+
+    fn right_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Right)
+    }
+
+    /// Stones interact only with orthogonally adjacent stones, and empty tiles never become
+    /// occupied again
+    fn decompositions(&self) -> Vec<Self> {
+        decompositions(&self.grid, Stone::is_occupied, Stone::Empty, &DIRECTIONS)
+            .into_iter()
+            .map(Self::new)
+            .collect::<Vec<_>>()
+    }
+}
+
+impl<G> SymmetricPosition for Clobber<G>
+where
+    G: Grid<Item = Stone> + FiniteGrid,
+{
+    fn transform(&self, symmetry: Symmetry) -> Self {
+        Self::new(symmetry.apply(&self.grid))
+    }
+
+    fn swaps_players(_symmetry: Symmetry) -> bool {
+        // Stones clobber orthogonally adjacent stones, and every symmetry keeps adjacency
+        false
+    }
+}
+
+impl<G> Encode for Clobber<G>
+where
+    G: Encode,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.grid.encode(buf);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        G::decode(input).map(|grid| Self { grid })
+    }
+}
+
+impl<G> Display for Clobber<G>
+where
+    G: Grid<Item = Stone> + FiniteGrid,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.grid.display(f, '|')
+    }
+}
+
+impl<G> FromStr for Clobber<G>
+where
+    G: Grid<Item = Stone> + FiniteGrid,
+{
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(G::parse(s).ok_or(())?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::transposition_table::ParallelTranspositionTable;
+
+    #[test]
+    fn moves() {
+        let position: Clobber = Clobber::from_str("xo.|ox.").unwrap();
+        assert_eq!(
+            position.left_moves(),
+            vec![
+                Clobber::from_str(".x.|ox.").unwrap(),
+                Clobber::from_str(".o.|xx.").unwrap(),
+                Clobber::from_str("xo.|x..").unwrap(),
+                Clobber::from_str("xx.|o..").unwrap(),
+            ]
+        );
+        assert_eq!(position.decompositions().len(), 1);
+
+        let position: Clobber = Clobber::from_str("xo.x|...o").unwrap();
+        assert_eq!(
+            position.decompositions(),
+            vec![
+                Clobber::from_str("xo").unwrap(),
+                Clobber::from_str("x|o").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn values() {
+        let tt = ParallelTranspositionTable::new();
+        for (position, value) in [
+            // Linear Clobber
+            ("xo", "*"),
+            ("xxo", "^"),
+            ("xoo", "v"),
+            ("xoxo", "{*, ^|v, *}"),
+            ("x.o", "0"),
+            // Grid Clobber
+            ("xo|ox", "*"),
+            ("xx|oo", "0"),
+        ] {
+            let position: Clobber = Clobber::from_str(position).unwrap();
+            assert_eq!(
+                position.canonical_form(&tt).to_string(),
+                value,
+                "{position}"
+            );
+        }
+    }
+}
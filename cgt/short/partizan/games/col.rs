@@ -0,0 +1,91 @@
+//! Col is played on undirected graph. In each turn Left colors a vertex blue and Right colors
+//! a vertex red. Players can only choose a vertex that is adjacent to only empty vertices or to
+//! vertices in the opponent's color.
+//!
+//! Values of Col positions are always numbers or switches, i.e. numbers plus a star.
+
+use crate::short::partizan::games::node_coloring::{ColoringRule, NodeColoring};
+pub use crate::short::partizan::games::node_coloring::{VertexColor, VertexKind};
+
+/// Ruleset of [col](self), players cannot color vertices adjacent to their own color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ColRule;
+
+impl ColoringRule for ColRule {
+    const OWN_ADJACENT: bool = false;
+    const OPPONENT_ADJACENT: bool = true;
+}
+
+/// Position of a [col](self) game
+pub type Col<V, G> = NodeColoring<ColRule, V, G>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::{Graph, VertexIndex, adjacency_matrix::undirected::UndirectedGraph},
+        short::partizan::{
+            games::snort::Snort, partizan_game::PartizanGame,
+            transposition_table::ParallelTranspositionTable,
+        },
+    };
+    use std::num::NonZeroU32;
+
+    fn path(vertices: &[VertexKind]) -> UndirectedGraph<VertexKind> {
+        let edges = (1..vertices.len())
+            .map(|index| (VertexIndex { index: index - 1 }, VertexIndex { index }))
+            .collect::<Vec<_>>();
+        UndirectedGraph::from_edges(&edges, vertices)
+    }
+
+    #[test]
+    fn col_values() {
+        let empty = VertexKind::Single(VertexColor::Empty);
+        let tint_left = VertexKind::Single(VertexColor::TintLeft);
+        let tint_right = VertexKind::Single(VertexColor::TintRight);
+
+        let tt = ParallelTranspositionTable::new();
+        for (vertices, value) in [
+            (vec![empty], "*"),
+            (vec![tint_left], "-1"),
+            (vec![tint_right], "1"),
+            (vec![empty, empty], "0"),
+            (vec![empty, empty, empty], "0"),
+            (vec![tint_left, empty], "-1/2"),
+            (vec![tint_right, empty, tint_right], "1"),
+        ] {
+            let position: Col<VertexKind, UndirectedGraph<VertexKind>> = Col::new(path(&vertices));
+            assert_eq!(
+                position.canonical_form(&tt).to_string(),
+                value,
+                "{vertices:?}"
+            );
+        }
+
+        let position: Col<VertexKind, UndirectedGraph<VertexKind>> =
+            Col::new(UndirectedGraph::empty(&[VertexKind::Cluster(
+                VertexColor::TintLeft,
+                NonZeroU32::new(5).unwrap(),
+            )]));
+        assert_eq!(position.canonical_form(&tt).to_string(), "-5");
+    }
+
+    #[test]
+    fn same_graph_as_snort() {
+        let graph = path(&[VertexKind::Single(VertexColor::Empty); 2]);
+
+        let col: Col<VertexKind, UndirectedGraph<VertexKind>> = Col::new(graph.clone());
+        let snort: Snort<VertexKind, UndirectedGraph<VertexKind>> = Snort::new(graph);
+        assert_eq!(
+            col.canonical_form(&ParallelTranspositionTable::new())
+                .to_string(),
+            "0"
+        );
+        assert_eq!(
+            snort
+                .canonical_form(&ParallelTranspositionTable::new())
+                .to_string(),
+            "{1|-1}"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! Cutcake is played on a collection of rectangular pieces of cake. In each turn Left cuts one
+//! of the pieces vertically and Right cuts one of the pieces horizontally, along the lines between
+//! squares.
+//!
+//! Every Cutcake position is an integer.
+
+use crate::short::partizan::{
+    Player,
+    cake_game::{CakeGame, CakePosition, Rectangle},
+    canonical_form::CanonicalForm,
+};
+use std::fmt::{self, Display};
+
+/// Cutcake ruleset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cutcake;
+
+impl Display for Cutcake {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Cutcake")
+    }
+}
+
+impl Cutcake {
+    /// Create new Cutcake position from pieces
+    #[inline]
+    pub fn position(pieces: Vec<Rectangle>) -> CakePosition<Self> {
+        CakePosition::new(Self, pieces)
+    }
+
+    /// Value of a piece, see Winning Ways, chapter 2. For `width >= height` the value is
+    /// `floor(width / 2^k) - 1`, where `2^k` is the largest power of two not exceeding `height`.
+    fn value(piece: Rectangle) -> i64 {
+        if piece.width >= piece.height {
+            let halvings = piece.height.ilog2();
+            i64::from(piece.width >> halvings) - 1
+        } else {
+            -Self::value(piece.transpose())
+        }
+    }
+}
+
+impl CakeGame for Cutcake {
+    fn piece_moves(&self, piece: Rectangle, player: Player) -> Vec<Vec<Rectangle>> {
+        match player {
+            Player::Left => (1..=piece.width / 2)
+                .map(|left| {
+                    vec![
+                        Rectangle::new(left, piece.height),
+                        Rectangle::new(piece.width - left, piece.height),
+                    ]
+                })
+                .collect(),
+            Player::Right => self
+                .piece_moves(piece.transpose(), Player::Left)
+                .into_iter()
+                .map(|pieces| pieces.into_iter().map(Rectangle::transpose).collect())
+                .collect(),
+        }
+    }
+
+    fn piece_value(&self, piece: Rectangle) -> Option<CanonicalForm> {
+        Some(CanonicalForm::new_integer(Self::value(piece)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        drawing::{Draw, svg},
+        short::partizan::{
+            partizan_game::PartizanGame, transposition_table::ParallelTranspositionTable,
+        },
+    };
+
+    #[test]
+    fn closed_form_matches_moves() {
+        let tt = ParallelTranspositionTable::new();
+        for width in 1..=12 {
+            for height in 1..=12 {
+                let position = Cutcake::position(vec![Rectangle::new(width, height)]);
+                let from_moves = CanonicalForm::new_from_moves(
+                    position
+                        .left_moves()
+                        .iter()
+                        .map(|m| m.canonical_form(&tt))
+                        .collect(),
+                    position
+                        .right_moves()
+                        .iter()
+                        .map(|m| m.canonical_form(&tt))
+                        .collect(),
+                );
+                assert_eq!(position.canonical_form(&tt), from_moves, "{width}x{height}");
+            }
+        }
+    }
+
+    #[test]
+    fn values() {
+        let tt = ParallelTranspositionTable::new();
+        for (pieces, value) in [
+            (vec![Rectangle::new(1, 1)], "0"),
+            (vec![Rectangle::new(5, 1)], "4"),
+            (vec![Rectangle::new(1, 5)], "-4"),
+            (vec![Rectangle::new(4, 2)], "1"),
+            (vec![Rectangle::new(8, 3)], "3"),
+            (vec![Rectangle::new(4, 2), Rectangle::new(2, 8)], "-2"),
+        ] {
+            let position = Cutcake::position(pieces);
+            assert_eq!(
+                position.canonical_form(&tt).to_string(),
+                value,
+                "{position}"
+            );
+        }
+    }
+
+    #[test]
+    fn wide_pieces_canvas() {
+        let narrow = Cutcake::position(vec![Rectangle::new(44, 1)]);
+        let wide = Cutcake::position(vec![Rectangle::new(300, 1)]);
+        assert!(
+            wide.required_canvas::<svg::Canvas>().bottom_right.x
+                > narrow.required_canvas::<svg::Canvas>().bottom_right.x
+        );
+    }
+}
@@ -4,8 +4,9 @@
 extern crate alloc;
 use crate::{
     drawing::{self, BoundingBox, Canvas, Color, Draw},
-    grid::{self, FiniteGrid, Grid, decompositions, small_bit_grid::SmallBitGrid},
-    short::partizan::partizan_game::PartizanGame,
+    encoding::Encode,
+    grid::{self, FiniteGrid, Grid, Symmetry, decompositions, small_bit_grid::SmallBitGrid},
+    short::partizan::{partizan_game::PartizanGame, symmetry::SymmetricPosition},
 };
 use cgt_derive::Tile;
 use core::hash::Hash;
@@ -38,6 +39,33 @@ pub struct Domineering<G = SmallBitGrid<Tile>> {
     grid: G,
 }
 
+impl<G> SymmetricPosition for Domineering<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid,
+{
+    fn transform(&self, symmetry: Symmetry) -> Self {
+        Self::new(symmetry.apply(&self.grid))
+    }
+
+    fn swaps_players(symmetry: Symmetry) -> bool {
+        // Left and Right dominoes are swapped when the board is rotated by 90 degrees
+        symmetry.swaps_axes()
+    }
+}
+
+impl<G> Encode for Domineering<G>
+where
+    G: Encode,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.grid.encode(buf);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        G::decode(input).map(|grid| Self { grid })
+    }
+}
+
 impl<G> Display for Domineering<G>
 where
     G: Grid<Item = Tile> + FiniteGrid,
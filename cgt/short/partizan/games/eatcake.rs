@@ -0,0 +1,74 @@
+//! Eatcake is played on a collection of rectangular pieces of cake. In each turn Left eats a whole
+//! column of one of the pieces and Right eats a whole row, which may split the piece in two.
+
+use crate::short::partizan::{
+    Player,
+    cake_game::{CakeGame, CakePosition, Rectangle},
+};
+use std::fmt::{self, Display};
+
+/// Eatcake ruleset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Eatcake;
+
+impl Display for Eatcake {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Eatcake")
+    }
+}
+
+impl Eatcake {
+    /// Create new Eatcake position from pieces
+    #[inline]
+    pub fn position(pieces: Vec<Rectangle>) -> CakePosition<Self> {
+        CakePosition::new(Self, pieces)
+    }
+}
+
+impl CakeGame for Eatcake {
+    fn piece_moves(&self, piece: Rectangle, player: Player) -> Vec<Vec<Rectangle>> {
+        match player {
+            // Eating column `i` and `width - 1 - i` gives the same pieces
+            Player::Left => (0..piece.width.div_ceil(2))
+                .map(|left| {
+                    vec![
+                        Rectangle::new(left, piece.height),
+                        Rectangle::new(piece.width - 1 - left, piece.height),
+                    ]
+                })
+                .collect(),
+            Player::Right => self
+                .piece_moves(piece.transpose(), Player::Left)
+                .into_iter()
+                .map(|pieces| pieces.into_iter().map(Rectangle::transpose).collect())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::{
+        partizan_game::PartizanGame, transposition_table::ParallelTranspositionTable,
+    };
+
+    #[test]
+    fn values() {
+        let tt = ParallelTranspositionTable::new();
+        for (pieces, value) in [
+            (vec![Rectangle::new(1, 1)], "*"),
+            (vec![Rectangle::new(2, 1)], "v"),
+            (vec![Rectangle::new(1, 2)], "^"),
+            (vec![Rectangle::new(2, 2)], "*"),
+        ] {
+            let position = Eatcake::position(pieces);
+            assert_eq!(
+                position.canonical_form(&tt).to_string(),
+                value,
+                "{position}"
+            );
+        }
+    }
+}
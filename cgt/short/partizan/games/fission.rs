@@ -7,8 +7,9 @@
 
 use crate::{
     drawing::{self, BoundingBox, Canvas, Color, Draw},
-    grid::{FiniteGrid, Grid, vec_grid::VecGrid},
-    short::partizan::partizan_game::PartizanGame,
+    encoding::Encode,
+    grid::{FiniteGrid, Grid, Symmetry, vec_grid::VecGrid},
+    short::partizan::{partizan_game::PartizanGame, symmetry::SymmetricPosition},
 };
 use cgt_derive::Tile;
 use std::{fmt::Display, hash::Hash, str::FromStr};
@@ -38,6 +39,33 @@ pub struct Fission<G = VecGrid<Tile>> {
     grid: G,
 }
 
+impl<G> SymmetricPosition for Fission<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid,
+{
+    fn transform(&self, symmetry: Symmetry) -> Self {
+        Self::new(symmetry.apply(&self.grid))
+    }
+
+    fn swaps_players(symmetry: Symmetry) -> bool {
+        // Vertical and horizontal moves are swapped when the board is rotated by 90 degrees
+        symmetry.swaps_axes()
+    }
+}
+
+impl<G> Encode for Fission<G>
+where
+    G: Encode,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.grid.encode(buf);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        G::decode(input).map(|grid| Self { grid })
+    }
+}
+
 impl<G> Display for Fission<G>
 where
     G: Grid<Item = Tile> + FiniteGrid,
@@ -0,0 +1,433 @@
+//! Go endgame is played on a small region of a Go board. Left plays Black and Right plays White,
+//! in each turn placing a stone of their color on an empty point. Opponent's groups left without
+//! liberties are captured and suicide is not allowed.
+//!
+//! Region is delimited by walls (and the edge of the grid) that give no liberties, and by safe
+//! stones that are assumed to be alive, i.e. they can never be captured. Groups connected to a
+//! safe stone are safe as well.
+//!
+//! Values follow Japanese-style territory counting: every empty point a player can fill and every
+//! prisoner counts as a point. Chilling by one point gives the values used in "Mathematical Go"
+//! by Berlekamp and Wolfe.
+//!
+//! Positions are assumed to be ko-free. To keep the game finite, captures that create a ko shape
+//! (single stone capturing a single stone and having the captured point as its only liberty) are
+//! not allowed.
+
+use crate::{
+    drawing::{self, BoundingBox, Canvas, Color, Draw},
+    grid::{self, FiniteGrid, Grid, vec_grid::VecGrid},
+    numeric::dyadic_rational_number::DyadicRationalNumber,
+    short::partizan::{
+        Player, canonical_form::CanonicalForm, partizan_game::PartizanGame,
+        transposition_table::TranspositionTable,
+    },
+};
+use cgt_derive::Tile;
+use std::{
+    collections::VecDeque,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// Point on the Go endgame grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Tile)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tile {
+    /// Empty point
+    #[tile(default, char('.'))]
+    Empty,
+
+    /// Left player's stone
+    #[tile(char('x'))]
+    Black,
+
+    /// Right player's stone
+    #[tile(char('o'))]
+    White,
+
+    /// Left player's stone that cannot be captured
+    #[tile(char('X'))]
+    SafeBlack,
+
+    /// Right player's stone that cannot be captured
+    #[tile(char('O'))]
+    SafeWhite,
+
+    /// Point outside of the region
+    #[tile(char('#'))]
+    Wall,
+}
+
+impl Tile {
+    /// Get the player owning the stone, if any
+    #[inline]
+    pub const fn owner(self) -> Option<Player> {
+        match self {
+            Self::Black | Self::SafeBlack => Some(Player::Left),
+            Self::White | Self::SafeWhite => Some(Player::Right),
+            Self::Empty | Self::Wall => None,
+        }
+    }
+
+    /// Check if the stone can never be captured
+    #[inline]
+    pub const fn is_safe(self) -> bool {
+        matches!(self, Self::SafeBlack | Self::SafeWhite)
+    }
+
+    /// Check if the point is part of the played region, i.e. it's empty or has a stone that can
+    /// be captured
+    #[inline]
+    const fn is_active(self) -> bool {
+        matches!(self, Self::Empty | Self::Black | Self::White)
+    }
+
+    #[inline]
+    const fn stone(player: Player) -> Self {
+        match player {
+            Player::Left => Self::Black,
+            Player::Right => Self::White,
+        }
+    }
+}
+
+/// Go endgame region, see [`go_endgame`](self) header
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GoEndgame {
+    grid: VecGrid<Tile>,
+
+    /// Number of stones captured by Left minus number of stones captured by Right
+    prisoners: i64,
+}
+
+impl GoEndgame {
+    /// Create new Go endgame position from a grid, without any prisoners
+    #[inline]
+    pub const fn new(grid: VecGrid<Tile>) -> Self {
+        Self { grid, prisoners: 0 }
+    }
+
+    /// Get underlying grid
+    #[inline]
+    pub const fn grid(&self) -> &VecGrid<Tile> {
+        &self.grid
+    }
+
+    /// Get number of stones captured by Left minus number of stones captured by Right
+    #[inline]
+    pub const fn prisoners(&self) -> i64 {
+        self.prisoners
+    }
+
+    /// Get the value of the position chilled by one point, as in "Mathematical Go"
+    pub fn chilled_value<TT>(&self, transposition_table: &TT) -> CanonicalForm
+    where
+        TT: TranspositionTable<Self> + Sync,
+    {
+        self.canonical_form(transposition_table)
+            .cool(DyadicRationalNumber::from(1))
+    }
+
+    fn neighbours(&self, x: u8, y: u8) -> impl Iterator<Item = (u8, u8)> + use<> {
+        let (width, height) = (self.grid.width(), self.grid.height());
+        [(0, -1), (0, 1), (-1, 0), (1, 0)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| {
+                let nx = x.checked_add_signed(dx)?;
+                let ny = y.checked_add_signed(dy)?;
+                (nx < width && ny < height).then_some((nx, ny))
+            })
+    }
+
+    /// Get stones of the group at given point and check if it's captured, i.e. it has no
+    /// liberties and is not connected to a safe stone
+    fn group(&self, x: u8, y: u8) -> (Vec<(u8, u8)>, bool) {
+        let owner = self.grid.get(x, y).owner();
+        let mut visited = vec![false; self.grid.width() as usize * self.grid.height() as usize];
+        let index = |x: u8, y: u8| y as usize * self.grid.width() as usize + x as usize;
+
+        let mut stones = Vec::new();
+        let mut captured = true;
+        let mut queue = VecDeque::from([(x, y)]);
+        visited[index(x, y)] = true;
+        while let Some((x, y)) = queue.pop_front() {
+            let tile = self.grid.get(x, y);
+            captured &= !tile.is_safe();
+            stones.push((x, y));
+            for (nx, ny) in self.neighbours(x, y) {
+                let neighbour = self.grid.get(nx, ny);
+                captured &= neighbour != Tile::Empty;
+                if neighbour.owner() == owner && !visited[index(nx, ny)] {
+                    visited[index(nx, ny)] = true;
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+        (stones, captured)
+    }
+
+    /// Get position after placing a stone, or `None` if the move is illegal
+    pub fn place_stone(&self, x: u8, y: u8, player: Player) -> Option<Self> {
+        if self.grid.get(x, y) != Tile::Empty {
+            return None;
+        }
+
+        let mut new = self.clone();
+        new.grid.set(x, y, Tile::stone(player));
+
+        let mut captured_stones = 0;
+        let mut captured_point = (x, y);
+        for (nx, ny) in self.neighbours(x, y) {
+            if new.grid.get(nx, ny).owner() == Some(player.opposite()) {
+                let (stones, captured) = new.group(nx, ny);
+                if captured {
+                    captured_stones += stones.len() as i64;
+                    captured_point = (nx, ny);
+                    for (sx, sy) in stones {
+                        new.grid.set(sx, sy, Tile::Empty);
+                    }
+                }
+            }
+        }
+
+        let (stones, captured) = new.group(x, y);
+        if captured {
+            // Suicide
+            return None;
+        }
+
+        let liberties = new
+            .neighbours(x, y)
+            .filter(|&(nx, ny)| new.grid.get(nx, ny) == Tile::Empty)
+            .collect::<Vec<_>>();
+        if captured_stones == 1 && stones.len() == 1 && liberties == [captured_point] {
+            // Ko
+            return None;
+        }
+
+        new.prisoners += match player {
+            Player::Left => captured_stones,
+            Player::Right => -captured_stones,
+        };
+        Some(new)
+    }
+
+    fn moves_for(&self, player: Player) -> Vec<Self> {
+        let mut moves = Vec::new();
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                if let Some(new) = self.place_stone(x, y, player) {
+                    moves.push(new);
+                }
+            }
+        }
+
+        // Prisoners are an integer that is summed with the region
+        match player {
+            Player::Left if self.prisoners > 0 => moves.push(Self {
+                grid: self.grid.clone(),
+                prisoners: self.prisoners - 1,
+            }),
+            Player::Right if self.prisoners < 0 => moves.push(Self {
+                grid: self.grid.clone(),
+                prisoners: self.prisoners + 1,
+            }),
+            _ => {}
+        }
+
+        moves
+    }
+}
+
+impl FromStr for GoEndgame {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(VecGrid::parse(s).ok_or(())?))
+    }
+}
+
+impl Display for GoEndgame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.grid.display(f, '|')?;
+        if self.prisoners != 0 {
+            write!(f, " {:+}", self.prisoners)?;
+        }
+        Ok(())
+    }
+}
+
+impl Draw for GoEndgame {
+    fn draw<C>(&self, canvas: &mut C)
+    where
+        C: Canvas,
+    {
+        self.grid.draw(canvas, |tile| match tile {
+            Tile::Empty => drawing::Tile::Square {
+                color: Color::LIGHT_GRAY,
+            },
+            Tile::Black => drawing::Tile::Circle {
+                tile_color: Color::LIGHT_GRAY,
+                circle_color: Color::BLACK,
+            },
+            Tile::White => drawing::Tile::Circle {
+                tile_color: Color::LIGHT_GRAY,
+                circle_color: Color::WHITE,
+            },
+            Tile::SafeBlack => drawing::Tile::Circle {
+                tile_color: Color::DARK_GRAY,
+                circle_color: Color::BLACK,
+            },
+            Tile::SafeWhite => drawing::Tile::Circle {
+                tile_color: Color::DARK_GRAY,
+                circle_color: Color::WHITE,
+            },
+            Tile::Wall => drawing::Tile::Square {
+                color: Color::DARK_GRAY,
+            },
+        });
+    }
+
+    fn required_canvas<C>(&self) -> BoundingBox
+    where
+        C: Canvas,
+    {
+        self.grid.canvas_size::<C>()
+    }
+}
+
+impl PartizanGame for GoEndgame {
+    fn left_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Left)
+    }
+
+    fn right_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Right)
+    }
+
+    /// Connected parts of the region together with adjacent safe stones, and prisoners as a
+    /// separate position. Parts connected only through safe stones are independent, as safe
+    /// stones always have liberties.
+    fn decompositions(&self) -> Vec<Self> {
+        let (width, height) = (self.grid.width(), self.grid.height());
+        let index = |x: u8, y: u8| y as usize * width as usize + x as usize;
+        let mut visited = vec![false; width as usize * height as usize];
+        let mut decompositions = Vec::new();
+
+        for y in 0..height {
+            for x in 0..width {
+                if visited[index(x, y)] || !self.grid.get(x, y).is_active() {
+                    continue;
+                }
+
+                let mut part = VecGrid::filled(width, height, Tile::Wall).unwrap();
+                let mut queue = VecDeque::from([(x, y)]);
+                visited[index(x, y)] = true;
+                while let Some((x, y)) = queue.pop_front() {
+                    part.set(x, y, self.grid.get(x, y));
+                    for (nx, ny) in self.neighbours(x, y) {
+                        let neighbour = self.grid.get(nx, ny);
+                        if neighbour.is_safe() {
+                            part.set(nx, ny, neighbour);
+                        } else if neighbour.is_active() && !visited[index(nx, ny)] {
+                            visited[index(nx, ny)] = true;
+                            queue.push_back((nx, ny));
+                        }
+                    }
+                }
+
+                decompositions.push(Self::new(grid::move_top_left(&part, |tile| {
+                    tile != Tile::Wall
+                })));
+            }
+        }
+
+        if self.prisoners != 0 {
+            decompositions.push(Self {
+                grid: VecGrid::zero_size(),
+                prisoners: self.prisoners,
+            });
+        }
+
+        decompositions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::transposition_table::ParallelTranspositionTable;
+
+    macro_rules! go {
+        ($inp:expr) => {
+            GoEndgame::from_str($inp).expect("invalid grid")
+        };
+    }
+
+    fn value(position: &str) -> String {
+        let tt = ParallelTranspositionTable::new();
+        go!(position).canonical_form(&tt).to_string()
+    }
+
+    fn chilled(position: &str) -> String {
+        let tt = ParallelTranspositionTable::new();
+        go!(position).chilled_value(&tt).to_string()
+    }
+
+    #[test]
+    fn territory() {
+        // Every point of territory is a move that only its owner can make
+        assert_eq!(value("XXXX|X..X|XXXX"), "2");
+        assert_eq!(value("OOO|O.O|OOO"), "-1");
+
+        // Dame can be filled by both players
+        assert_eq!(value("X.O"), "*");
+        assert_eq!(chilled("X.O"), "0");
+    }
+
+    #[test]
+    fn captures() {
+        // Captured stone is a prisoner and its point becomes territory
+        let position = go!("XXX#|XoX#|X.X#");
+        let captured = position.place_stone(1, 2, Player::Left).unwrap();
+        assert_eq!(captured.to_string(), "XXX#|X.X#|XxX# +1");
+        assert_eq!(captured.prisoners(), 1);
+        assert_eq!(
+            captured.decompositions(),
+            vec![
+                go!("#X#|X.X|XxX"),
+                GoEndgame {
+                    grid: VecGrid::zero_size(),
+                    prisoners: 1
+                }
+            ]
+        );
+
+        // White can't play its last liberty
+        assert_eq!(position.place_stone(1, 2, Player::Right), None);
+        assert_eq!(value("XXX#|XoX#|X.X#"), "3");
+    }
+
+    #[test]
+    fn ko_is_forbidden() {
+        let position = go!("#XO#|X.xO|#XO#");
+        assert_eq!(position.place_stone(1, 1, Player::Right), None);
+        assert!(position.place_stone(1, 1, Player::Left).is_some());
+    }
+
+    #[test]
+    fn corridors() {
+        // Chilled value of Black's corridor of length `n` with White's stone at the mouth is
+        // `n - 2 + 2^(1 - n)`
+        for (corridor, expected) in [
+            ("XXX|O.X|XXX", "0"),
+            ("XXXX|O..X|XXXX", "1/2"),
+            ("XXXXX|O...X|XXXXX", "5/4"),
+            ("XXXXXX|O....X|XXXXXX", "17/8"),
+        ] {
+            assert_eq!(chilled(corridor), expected, "{corridor}");
+        }
+    }
+}
@@ -0,0 +1,468 @@
+//! Blue-Red-Green Hackenbush is played on a graph with colored edges, where one vertex is the
+//! ground. Left removes a blue or green edge and Right removes a red or green edge. After every
+//! move all edges that are no longer connected to the ground disappear.
+//!
+//! Graph is simple, so loops should be modelled as edges to a new leaf vertex, which behave the
+//! same way.
+
+use crate::{
+    drawing::{BoundingBox, Canvas, Color, Draw},
+    graph::{Graph, VertexIndex, adjacency_matrix::undirected::UndirectedGraph},
+    has::Has,
+    numeric::{dyadic_rational_number::DyadicRationalNumber, nimber::Nimber, v2f::V2f},
+    short::partizan::{Player, canonical_form::CanonicalForm, partizan_game::PartizanGame},
+};
+use std::{collections::VecDeque, hash::Hash};
+
+/// Color of Hackenbush edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EdgeColor {
+    /// Edge that only Left can remove
+    Blue,
+
+    /// Edge that only Right can remove
+    Red,
+
+    /// Edge that both players can remove
+    Green,
+}
+
+impl EdgeColor {
+    /// Check if player can remove edge of this color
+    #[inline]
+    pub const fn removable_by(self, player: Player) -> bool {
+        matches!(
+            (self, player),
+            (Self::Green, _) | (Self::Blue, Player::Left) | (Self::Red, Player::Right)
+        )
+    }
+}
+
+/// Position of a [hackenbush](self) game
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hackenbush<V = ()> {
+    // Invariant: every vertex is connected to the ground
+    graph: UndirectedGraph<V>,
+
+    // Color of edge between `u` and `v` is stored at `u * size + v`, `None` if not adjacent
+    edge_colors: Vec<Option<EdgeColor>>,
+}
+
+impl<V> Hackenbush<V>
+where
+    V: Clone,
+{
+    /// Ground vertex of every position
+    pub const GROUND: VertexIndex = VertexIndex { index: 0 };
+
+    /// Create new position from colored edges. First vertex is the ground, and vertices that are
+    /// not connected to the ground are removed.
+    ///
+    /// Returns `None` if there are no vertices, an edge is a loop or connects non-existing vertex.
+    pub fn from_edges(
+        vertices: &[V],
+        edges: &[(VertexIndex, VertexIndex, EdgeColor)],
+    ) -> Option<Self> {
+        let size = vertices.len();
+        if size == 0 {
+            return None;
+        }
+
+        let mut graph = UndirectedGraph::empty(vertices);
+        let mut edge_colors = vec![None; size * size];
+        for &(u, v, color) in edges {
+            if u == v || u.index >= size || v.index >= size {
+                return None;
+            }
+            graph.connect(u, v, true);
+            edge_colors[u.index * size + v.index] = Some(color);
+            edge_colors[v.index * size + u.index] = Some(color);
+        }
+
+        let position = Self { graph, edge_colors };
+        Some(position.retain_vertices(&position.grounded_vertices()))
+    }
+
+    /// Get the underlying graph
+    #[inline]
+    pub const fn graph(&self) -> &UndirectedGraph<V> {
+        &self.graph
+    }
+
+    /// Get color of the edge between two vertices, `None` if vertices are not adjacent
+    #[inline]
+    pub fn edge_color(
+        &self,
+        lhs_vertex: VertexIndex,
+        rhs_vertex: VertexIndex,
+    ) -> Option<EdgeColor> {
+        self.edge_colors[lhs_vertex.index * self.graph.size() + rhs_vertex.index]
+    }
+
+    /// Get iterator over colored edges
+    pub fn edges(&self) -> impl Iterator<Item = (VertexIndex, VertexIndex, EdgeColor)> + '_ {
+        self.graph
+            .edges()
+            .filter_map(|(u, v)| Some((u, v, self.edge_color(u, v)?)))
+    }
+
+    /// Remove edge and everything that is no longer connected to the ground. Note that it does
+    /// not check if the edge exists.
+    #[must_use]
+    pub fn remove_edge(&self, lhs_vertex: VertexIndex, rhs_vertex: VertexIndex) -> Self {
+        let size = self.graph.size();
+        let mut position = self.clone();
+        position.graph.connect(lhs_vertex, rhs_vertex, false);
+        position.edge_colors[lhs_vertex.index * size + rhs_vertex.index] = None;
+        position.edge_colors[rhs_vertex.index * size + lhs_vertex.index] = None;
+        position.retain_vertices(&position.grounded_vertices())
+    }
+
+    fn moves_for(&self, player: Player) -> Vec<Self> {
+        self.edges()
+            .filter(|(_, _, color)| color.removable_by(player))
+            .map(|(u, v, _)| self.remove_edge(u, v))
+            .collect()
+    }
+
+    /// Mark vertices reachable from the ground
+    fn grounded_vertices(&self) -> Vec<bool> {
+        let mut grounded = vec![false; self.graph.size()];
+        grounded[Self::GROUND.index] = true;
+        self.visit_from(Self::GROUND, &mut grounded);
+        grounded
+    }
+
+    /// Mark vertices reachable from the given one, without going through already marked vertices
+    fn visit_from(&self, start: VertexIndex, visited: &mut [bool]) {
+        let mut queue = VecDeque::from([start]);
+        while let Some(vertex) = queue.pop_front() {
+            for adjacent in self.graph.adjacent_to(vertex) {
+                if !visited[adjacent.index] {
+                    visited[adjacent.index] = true;
+                    queue.push_back(adjacent);
+                }
+            }
+        }
+    }
+
+    /// Create position with only selected vertices and edges between them
+    fn retain_vertices(&self, retain: &[bool]) -> Self {
+        if retain.iter().all(|retained| *retained) {
+            return self.clone();
+        }
+
+        let old_indices = self
+            .graph
+            .vertex_indices()
+            .filter(|vertex| retain[vertex.index])
+            .collect::<Vec<_>>();
+        let vertices = old_indices
+            .iter()
+            .map(|vertex| self.graph.get_vertex(*vertex).clone())
+            .collect::<Vec<_>>();
+
+        let size = vertices.len();
+        let mut graph = UndirectedGraph::empty(&vertices);
+        let mut edge_colors = vec![None; size * size];
+        for (new_u, old_u) in old_indices.iter().enumerate() {
+            for (new_v, old_v) in old_indices.iter().enumerate() {
+                if let Some(color) = self.edge_color(*old_u, *old_v) {
+                    graph.connect(
+                        VertexIndex { index: new_u },
+                        VertexIndex { index: new_v },
+                        true,
+                    );
+                    edge_colors[new_u * size + new_v] = Some(color);
+                }
+            }
+        }
+
+        Self { graph, edge_colors }
+    }
+
+    /// Value of a component with known closed form: stalk of blue and red edges (sign expansion)
+    /// or tree of green edges (colon principle)
+    fn closed_form_value(&self) -> Option<CanonicalForm> {
+        let edges = self.edges().collect::<Vec<_>>();
+
+        // Every vertex is connected to the ground, so graph with one edge less than vertices is
+        // a tree
+        if edges.len() + 1 != self.graph.size() {
+            return None;
+        }
+
+        if edges.iter().all(|(_, _, color)| *color == EdgeColor::Green) {
+            let value = self.green_tree_value(Self::GROUND, None);
+            return Some(CanonicalForm::new_nimber(
+                DyadicRationalNumber::from(0),
+                Nimber::new(value),
+            ));
+        }
+
+        let mut colors = Vec::with_capacity(edges.len());
+        let (mut previous, mut current) = (None, Self::GROUND);
+        loop {
+            let mut next = self
+                .graph
+                .adjacent_to(current)
+                .filter(|vertex| Some(*vertex) != previous);
+            let Some(vertex) = next.next() else {
+                break;
+            };
+            if next.next().is_some() {
+                // Branching tree
+                return None;
+            }
+            match self.edge_color(current, vertex)? {
+                EdgeColor::Green => return None,
+                color => colors.push(color),
+            }
+            (previous, current) = (Some(current), vertex);
+        }
+
+        Some(CanonicalForm::new_dyadic(sign_expansion(&colors)))
+    }
+
+    /// Colon principle - green tree is equivalent to a stalk of length equal to nim sum of its
+    /// branches, each one edge longer than the subtree above it
+    fn green_tree_value(&self, vertex: VertexIndex, parent: Option<VertexIndex>) -> u32 {
+        self.graph
+            .adjacent_to(vertex)
+            .filter(|adjacent| Some(*adjacent) != parent)
+            .map(|child| self.green_tree_value(child, Some(vertex)) + 1)
+            .fold(0, |acc, branch| acc ^ branch)
+    }
+}
+
+impl Hackenbush<()> {
+    /// Create stalk of edges going up from the ground, with colors listed from the bottom
+    pub fn stalk(colors: &[EdgeColor]) -> Self {
+        let vertices = vec![(); colors.len() + 1];
+        let edges = colors
+            .iter()
+            .enumerate()
+            .map(|(idx, color)| {
+                (
+                    VertexIndex { index: idx },
+                    VertexIndex { index: idx + 1 },
+                    *color,
+                )
+            })
+            .collect::<Vec<_>>();
+        Self::from_edges(&vertices, &edges).expect("stalk is a valid position")
+    }
+}
+
+/// Value of blue-red stalk. Edges below the first color change are worth one move each, and
+/// every following edge is worth half of the previous one.
+fn sign_expansion(colors: &[EdgeColor]) -> DyadicRationalNumber {
+    let sign = |color: EdgeColor| if color == EdgeColor::Blue { 1 } else { -1 };
+
+    let Some(&first) = colors.first() else {
+        return DyadicRationalNumber::from(0);
+    };
+    let integer_part = colors.iter().take_while(|color| **color == first).count();
+
+    let mut value = DyadicRationalNumber::from(sign(first) * integer_part as i64);
+    for (exponent, color) in colors[integer_part..].iter().enumerate() {
+        value += DyadicRationalNumber::new(sign(*color), exponent as u32 + 1);
+    }
+    value
+}
+
+impl<V> PartizanGame for Hackenbush<V>
+where
+    V: Clone + Hash + Eq + Send + Sync,
+{
+    fn left_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Left)
+    }
+
+    fn right_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Right)
+    }
+
+    /// Decompose the position into parts that touch each other only at the ground
+    fn decompositions(&self) -> Vec<Self> {
+        let mut visited = vec![false; self.graph.size()];
+        visited[Self::GROUND.index] = true;
+
+        let mut res = Vec::new();
+        for vertex in self.graph.adjacent_to(Self::GROUND) {
+            if !visited[vertex.index] {
+                let mut component = vec![false; self.graph.size()];
+                component[Self::GROUND.index] = true;
+                component[vertex.index] = true;
+                visited[vertex.index] = true;
+
+                let before = visited.clone();
+                self.visit_from(vertex, &mut visited);
+                for (in_component, (now, before)) in
+                    component.iter_mut().zip(visited.iter().zip(before))
+                {
+                    *in_component |= *now && !before;
+                }
+                res.push(self.retain_vertices(&component));
+            }
+        }
+        res
+    }
+
+    fn reductions(&self) -> Option<CanonicalForm> {
+        self.decompositions()
+            .iter()
+            .map(Self::closed_form_value)
+            .sum()
+    }
+}
+
+impl<V> Draw for Hackenbush<V>
+where
+    V: Has<V2f> + Clone,
+{
+    fn draw<C>(&self, canvas: &mut C)
+    where
+        C: Canvas,
+    {
+        let position = |vertex| *self.graph.get_vertex(vertex).get_inner();
+        for (u, v, color) in self.edges() {
+            canvas.line(
+                position(u),
+                position(v),
+                C::thick_line_weight(),
+                match color {
+                    EdgeColor::Blue => Color::BLUE,
+                    EdgeColor::Red => Color::RED,
+                    EdgeColor::Green => Color::GREEN,
+                },
+            );
+        }
+
+        for vertex in self.graph.vertex_indices() {
+            let color = if vertex == Self::GROUND {
+                Color::DARK_GRAY
+            } else {
+                Color::LIGHT_GRAY
+            };
+            canvas.vertex(position(vertex), color, vertex);
+        }
+    }
+
+    fn required_canvas<C>(&self) -> BoundingBox
+    where
+        C: Canvas,
+    {
+        self.graph.required_canvas::<C>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::transposition_table::ParallelTranspositionTable;
+
+    use EdgeColor::{Blue, Green, Red};
+
+    fn edge(u: usize, v: usize, color: EdgeColor) -> (VertexIndex, VertexIndex, EdgeColor) {
+        (VertexIndex { index: u }, VertexIndex { index: v }, color)
+    }
+
+    /// Compute value from moves, so it does not depend on closed form of the position itself
+    fn value_from_moves(position: &Hackenbush) -> CanonicalForm {
+        let tt = ParallelTranspositionTable::new();
+        CanonicalForm::new_from_moves(
+            position
+                .left_moves()
+                .iter()
+                .map(|m| m.canonical_form(&tt))
+                .collect(),
+            position
+                .right_moves()
+                .iter()
+                .map(|m| m.canonical_form(&tt))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn stalks() {
+        for (colors, expected) in [
+            (&[Blue, Red, Red][..], "1/4"),
+            (&[Blue, Blue, Red], "3/2"),
+            (&[Blue, Red, Blue], "3/4"),
+            (&[Red, Red, Blue, Red], "-7/4"),
+            (&[Green, Green, Green], "*3"),
+            (&[Blue, Green], "1*"),
+            (&[], "0"),
+        ] {
+            let stalk = Hackenbush::stalk(colors);
+            let tt = ParallelTranspositionTable::new();
+            assert_eq!(
+                stalk.canonical_form(&tt).to_string(),
+                expected,
+                "{colors:?}"
+            );
+            assert_eq!(value_from_moves(&stalk).to_string(), expected, "{colors:?}");
+        }
+    }
+
+    #[test]
+    fn green_trees() {
+        // Two branches of length 1 on top of an edge are 1 + (1 xor 1) = 1
+        let tree = Hackenbush::from_edges(
+            &[(); 4],
+            &[edge(0, 1, Green), edge(1, 2, Green), edge(1, 3, Green)],
+        )
+        .unwrap();
+        assert_eq!(tree.reductions().unwrap().to_string(), "*");
+        assert_eq!(value_from_moves(&tree).to_string(), "*");
+
+        // Triangle fuses into a single edge
+        let triangle = Hackenbush::from_edges(
+            &[(); 3],
+            &[edge(0, 1, Green), edge(1, 2, Green), edge(2, 0, Green)],
+        )
+        .unwrap();
+        assert_eq!(triangle.reductions(), None);
+        let tt = ParallelTranspositionTable::new();
+        assert_eq!(triangle.canonical_form(&tt).to_string(), "*");
+    }
+
+    #[test]
+    fn ground_components() {
+        // Two stalks and a cycle, with vertex 6 not connected to the ground
+        let position = Hackenbush::from_edges(
+            &[(); 7],
+            &[
+                edge(0, 1, Blue),
+                edge(1, 2, Red),
+                edge(0, 3, Red),
+                edge(0, 4, Green),
+                edge(4, 5, Blue),
+                edge(5, 0, Red),
+            ],
+        )
+        .unwrap();
+        assert_eq!(position.graph().size(), 6);
+        assert_eq!(position.decompositions().len(), 3);
+        assert_eq!(position.reductions(), None);
+
+        let tt = ParallelTranspositionTable::new();
+        let expected = Hackenbush::stalk(&[Blue, Red]).canonical_form(&tt)
+            + Hackenbush::stalk(&[Red]).canonical_form(&tt)
+            + Hackenbush::from_edges(
+                &[(); 3],
+                &[edge(0, 1, Green), edge(1, 2, Blue), edge(2, 0, Red)],
+            )
+            .unwrap()
+            .canonical_form(&tt);
+        assert_eq!(position.canonical_form(&tt), expected);
+        assert_eq!(value_from_moves(&position), expected);
+
+        let removed = position.remove_edge(VertexIndex { index: 0 }, VertexIndex { index: 1 });
+        assert_eq!(removed.graph().size(), 4);
+    }
+}
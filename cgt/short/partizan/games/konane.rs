@@ -2,8 +2,9 @@
 
 use crate::{
     drawing::{self, BoundingBox, Canvas, Color, Draw},
-    grid::{FiniteGrid, Grid, vec_grid::VecGrid},
-    short::partizan::{Player, partizan_game::PartizanGame},
+    encoding::Encode,
+    grid::{FiniteGrid, Grid, Symmetry, vec_grid::VecGrid},
+    short::partizan::{Player, partizan_game::PartizanGame, symmetry::SymmetricPosition},
 };
 use cgt_derive::Tile;
 use std::{fmt::Display, hash::Hash, str::FromStr};
@@ -348,6 +349,33 @@ fn values() {
     assert_eq!(g.canonical_form(&tt).to_string(), "{1|-1}");
 }
 
+impl<G> SymmetricPosition for Konane<G>
+where
+    G: Grid<Item = Tile> + FiniteGrid,
+{
+    fn transform(&self, symmetry: Symmetry) -> Self {
+        Self::new(symmetry.apply(&self.grid))
+    }
+
+    fn swaps_players(_symmetry: Symmetry) -> bool {
+        // Both players jump along rows and columns, which every symmetry maps onto each other
+        false
+    }
+}
+
+impl<G> Encode for Konane<G>
+where
+    G: Encode,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.grid.encode(buf);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        G::decode(input).map(|grid| Self { grid })
+    }
+}
+
 impl<G> Display for Konane<G>
 where
     G: Grid<Item = Tile> + FiniteGrid,
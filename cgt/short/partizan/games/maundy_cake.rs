@@ -0,0 +1,95 @@
+//! Maundy Cake is played on a collection of rectangular pieces of cake. In each turn Left cuts one
+//! of the pieces vertically into a number of pieces of equal width, and Right cuts one of the
+//! pieces horizontally into a number of pieces of equal height.
+
+use crate::short::partizan::{
+    Player,
+    cake_game::{CakeGame, CakePosition, Rectangle},
+    canonical_form::CanonicalForm,
+};
+use std::fmt::{self, Display};
+
+/// Maundy Cake ruleset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaundyCake;
+
+impl Display for MaundyCake {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MaundyCake")
+    }
+}
+
+impl MaundyCake {
+    /// Create new Maundy Cake position from pieces
+    #[inline]
+    pub fn position(pieces: Vec<Rectangle>) -> CakePosition<Self> {
+        CakePosition::new(Self, pieces)
+    }
+
+    /// Number of cuts a player can make in a strip when the opponent has no moves
+    fn strip_moves(length: u32) -> i64 {
+        (2..=length)
+            .filter(|parts| length.is_multiple_of(*parts))
+            .map(|parts| 1 + i64::from(parts) * Self::strip_moves(length / parts))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl CakeGame for MaundyCake {
+    fn piece_moves(&self, piece: Rectangle, player: Player) -> Vec<Vec<Rectangle>> {
+        match player {
+            Player::Left => (2..=piece.width)
+                .filter(|parts| piece.width.is_multiple_of(*parts))
+                .map(|parts| {
+                    vec![Rectangle::new(piece.width / parts, piece.height); parts as usize]
+                })
+                .collect(),
+            Player::Right => self
+                .piece_moves(piece.transpose(), Player::Left)
+                .into_iter()
+                .map(|pieces| pieces.into_iter().map(Rectangle::transpose).collect())
+                .collect(),
+        }
+    }
+
+    /// Only strips, where one of the players has no moves, have closed form
+    fn piece_value(&self, piece: Rectangle) -> Option<CanonicalForm> {
+        if piece.height == 1 {
+            Some(CanonicalForm::new_integer(Self::strip_moves(piece.width)))
+        } else if piece.width == 1 {
+            Some(CanonicalForm::new_integer(-Self::strip_moves(piece.height)))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::{
+        partizan_game::PartizanGame, transposition_table::ParallelTranspositionTable,
+    };
+
+    #[test]
+    fn values() {
+        let tt = ParallelTranspositionTable::new();
+        for (pieces, value) in [
+            (vec![Rectangle::new(1, 1)], "0"),
+            (vec![Rectangle::new(4, 1)], "3"),
+            (vec![Rectangle::new(1, 6)], "-4"),
+            (vec![Rectangle::new(2, 2)], "0"),
+            (vec![Rectangle::new(4, 2)], "1"),
+            (vec![Rectangle::new(3, 2), Rectangle::new(2, 3)], "0"),
+        ] {
+            let position = MaundyCake::position(pieces);
+            assert_eq!(
+                position.canonical_form(&tt).to_string(),
+                value,
+                "{position}"
+            );
+        }
+    }
+}
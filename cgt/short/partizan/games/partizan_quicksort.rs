@@ -0,0 +1,159 @@
+//! This game is played on a sequence of numbers, and players move by pivoting around
+//! a number in the manner of the quicksort algorithm.
+//!
+//! This is partizan version of the game, where Left can pick only odd pivots and Right can pick
+//! only even pivots. For impartial version see [`quicksort`](crate::short::impartial::games::quicksort).
+//!
+//! This game has been proposed in [Andreas Chen's "The Quicksort Game"](https://www.diva-portal.org/smash/get/diva2:935354/FULLTEXT01.pdf>).
+
+use std::fmt::Display;
+
+use crate::{
+    display,
+    short::{
+        impartial::games::quicksort::sorted_block_slices,
+        partizan::{Player, partizan_game::PartizanGame},
+    },
+};
+
+/// See [`partizan_quicksort`](self) header
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartizanQuicksort {
+    sequence: Vec<u32>,
+}
+
+impl Display for PartizanQuicksort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PartizanQuicksort")?;
+        display::brackets(f, |f| display::commas(f, self.sequence()))
+    }
+}
+
+impl PartizanQuicksort {
+    /// Create new partizan quicksort position from a given sequence
+    #[inline]
+    pub const fn new(sequence: Vec<u32>) -> Self {
+        Self { sequence }
+    }
+
+    /// Get the sequence of the partizan quicksort position
+    #[inline]
+    pub const fn sequence(&self) -> &Vec<u32> {
+        &self.sequence
+    }
+
+    /// pivot on `pivot`
+    #[must_use]
+    pub fn pivot_on(&self, pivot: u32) -> Self {
+        let mut res = Self::new(Vec::with_capacity(self.sequence().len()));
+        res.sequence
+            .extend(self.sequence().iter().filter(|elem| **elem < pivot));
+        if self.sequence().contains(&pivot) {
+            res.sequence.push(pivot);
+        }
+        res.sequence
+            .extend(self.sequence().iter().filter(|elem| **elem > pivot));
+        res
+    }
+
+    fn moves_for(&self, player: Player) -> Vec<Self> {
+        let parity = match player {
+            Player::Left => 1,
+            Player::Right => 0,
+        };
+
+        let mut moves = Vec::with_capacity(self.sequence().len());
+        for pivot in self.sequence() {
+            if pivot % 2 != parity {
+                continue;
+            }
+            let new = self.pivot_on(*pivot);
+            if &new != self && !moves.contains(&new) {
+                moves.push(new);
+            }
+        }
+        moves
+    }
+}
+
+impl PartizanGame for PartizanQuicksort {
+    fn left_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Left)
+    }
+
+    fn right_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Right)
+    }
+
+    /// Sorted blocks of the sequence, shifted down by an even offset so the smallest element is
+    /// `0` or `1` and the parity of every element is preserved
+    fn decompositions(&self) -> Vec<Self> {
+        sorted_block_slices(self.sequence())
+            .into_iter()
+            .filter(|block| block.len() > 1)
+            .map(|block| {
+                let offset = block.iter().copied().min().unwrap() & !1;
+                Self::new(block.iter().map(|elem| elem - offset).collect())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::{
+        canonical_form::CanonicalForm, transposition_table::ParallelTranspositionTable,
+    };
+    use std::str::FromStr;
+
+    fn value(sequence: Vec<u32>) -> CanonicalForm {
+        let tt = ParallelTranspositionTable::new();
+        PartizanQuicksort::new(sequence).canonical_form(&tt)
+    }
+
+    #[test]
+    fn correct_canonical_forms() {
+        assert_eq!(value(vec![1, 2]), CanonicalForm::new_integer(0));
+
+        // Both players can only pivot to sorted sequence
+        assert_eq!(value(vec![2, 1]), CanonicalForm::from_str("*").unwrap());
+
+        // Left moves to [1, 3, 2] = * or [2, 1, 3] = *, Right sorts with 2
+        assert_eq!(
+            value(vec![3, 2, 1]),
+            CanonicalForm::from_str("{*|0}").unwrap()
+        );
+
+        // Left sorts with 1 or moves to [2, 1, 3] = *, Right sorts with 2
+        assert_eq!(
+            value(vec![2, 3, 1]),
+            CanonicalForm::from_str("{0, *|0}").unwrap()
+        );
+    }
+
+    #[test]
+    fn decomposes_into_blocks() {
+        let quicksort = PartizanQuicksort::new(vec![2, 1, 3, 6, 4, 5, 7, 9, 8]);
+        assert_eq!(
+            quicksort.decompositions(),
+            vec![
+                PartizanQuicksort::new(vec![2, 1]),
+                PartizanQuicksort::new(vec![2, 0, 1]),
+                PartizanQuicksort::new(vec![1, 0]),
+            ]
+        );
+        assert_eq!(
+            value(vec![2, 1, 3, 6, 4, 5, 7, 9, 8]),
+            value(vec![2, 1]) + value(vec![2, 0, 1]) + value(vec![1, 0])
+        );
+
+        // Blocks starting at zero are not shifted
+        assert_eq!(
+            PartizanQuicksort::new(vec![1, 0, 2]).decompositions(),
+            vec![PartizanQuicksort::new(vec![1, 0])]
+        );
+        assert_eq!(value(vec![1, 0]), value(vec![3, 2]));
+    }
+}
@@ -0,0 +1,330 @@
+//! Partizan subtraction game is played on a heap of tokens. In each turn player removes from the
+//! heap a number of tokens from their own subtraction set.
+//!
+//! For impartial version see [`subtraction`](crate::short::impartial::games::subtraction).
+
+use std::fmt::{self, Display};
+
+use crate::{
+    display,
+    short::{
+        impartial::grundy_sequence::find_period,
+        partizan::{Player, canonical_form::CanonicalForm, partizan_game::PartizanGame},
+    },
+};
+
+/// Partizan subtraction game ruleset with finite subtraction sets
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartizanSub {
+    // Invariant: sorted, without duplicates
+    left: Vec<u32>,
+
+    // Invariant: sorted, without duplicates
+    right: Vec<u32>,
+}
+
+impl Display for PartizanSub {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PartizanSub")?;
+        display::parens(f, |f| {
+            display::commas(f, self.left())?;
+            write!(f, " | ")?;
+            display::commas(f, self.right())
+        })
+    }
+}
+
+impl PartizanSub {
+    /// Define new partizan subtraction game with given subtraction sets. Zero subtractions are
+    /// ignored.
+    #[inline]
+    pub fn new(mut left: Vec<u32>, mut right: Vec<u32>) -> Self {
+        for set in [&mut left, &mut right] {
+            set.retain(|m| *m != 0);
+            set.sort_unstable();
+            set.dedup();
+        }
+        Self { left, right }
+    }
+
+    /// Get the subtraction set of Left
+    #[inline]
+    pub fn left(&self) -> &[u32] {
+        &self.left
+    }
+
+    /// Get the subtraction set of Right
+    #[inline]
+    pub fn right(&self) -> &[u32] {
+        &self.right
+    }
+
+    /// Get the subtraction set of a player
+    #[inline]
+    pub fn subtraction_set(&self, player: Player) -> &[u32] {
+        match player {
+            Player::Left => self.left(),
+            Player::Right => self.right(),
+        }
+    }
+
+    /// Get the largest subtraction of both players, or zero if both sets are empty
+    #[inline]
+    pub fn largest(&self) -> u32 {
+        let left = self.left().last().copied().unwrap_or(0);
+        let right = self.right().last().copied().unwrap_or(0);
+        left.max(right)
+    }
+
+    /// Create a position with a single heap
+    #[inline]
+    pub const fn position(self, heap: u32) -> PartizanSubPosition {
+        PartizanSubPosition {
+            ruleset: self,
+            heap,
+        }
+    }
+
+    /// Get the infinite sequence of canonical forms of heaps of size `0, 1, 2, ...`
+    #[inline]
+    pub fn canonical_forms(self) -> CanonicalFormSequence {
+        let previous = vec![CanonicalForm::new_integer(0); self.largest().max(1) as usize];
+        CanonicalFormSequence {
+            game: self,
+            previous,
+            current: 0,
+        }
+    }
+
+    /// Find the shortest arithmetic period that holds for heaps up to `max_heap`
+    ///
+    /// Saltus must be a number and period is reported only if it repeats for at least as many
+    /// values as the largest subtraction. This proves periodicity with zero saltus, as every value
+    /// depends only on that many previous values. Nonzero saltus is only observed and not proven.
+    pub fn detect_period(&self, max_heap: usize) -> Option<Periodicity> {
+        let largest = self.largest() as usize;
+        let values = self
+            .clone()
+            .canonical_forms()
+            .take(max_heap + 1)
+            .collect::<Vec<_>>();
+        let (preperiod, period, saltus) = find_period(
+            &values,
+            |previous, next| Some(next - previous).filter(CanonicalForm::is_number),
+            |preperiod, period, _| preperiod + period + largest <= values.len(),
+        )?;
+        Some(Periodicity {
+            preperiod,
+            period,
+            saltus,
+        })
+    }
+}
+
+/// Single heap of a partizan subtraction game
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartizanSubPosition {
+    ruleset: PartizanSub,
+    heap: u32,
+}
+
+impl Display for PartizanSubPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ruleset)?;
+        display::brackets(f, |f| write!(f, "{}", self.heap))
+    }
+}
+
+impl PartizanSubPosition {
+    /// Get the ruleset of the position
+    #[inline]
+    pub const fn ruleset(&self) -> &PartizanSub {
+        &self.ruleset
+    }
+
+    /// Get the size of the heap
+    #[inline]
+    pub const fn heap(&self) -> u32 {
+        self.heap
+    }
+
+    fn moves_for(&self, player: Player) -> Vec<Self> {
+        self.ruleset
+            .subtraction_set(player)
+            .iter()
+            .take_while(|&&m| m <= self.heap)
+            .map(|m| self.ruleset.clone().position(self.heap - m))
+            .collect()
+    }
+}
+
+impl PartizanGame for PartizanSubPosition {
+    fn left_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Left)
+    }
+
+    fn right_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Right)
+    }
+}
+
+/// Canonical forms of [`PartizanSub`] heaps iterator, computing every value from the previous
+/// ones
+#[derive(Debug, Clone)]
+pub struct CanonicalFormSequence {
+    /// The underlying subtraction game ruleset
+    game: PartizanSub,
+
+    /// Ring buffer of previous values
+    previous: Vec<CanonicalForm>,
+
+    /// Current heap size to compute canonical form for
+    current: u32,
+}
+
+impl Iterator for CanonicalFormSequence {
+    type Item = CanonicalForm;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let buffer_len = self.previous.len() as u32;
+
+        let options = |set: &[u32]| {
+            set.iter()
+                .take_while(|&&m| m <= self.current)
+                .map(|m| self.previous[((self.current - m) % buffer_len) as usize].clone())
+                .collect::<Vec<_>>()
+        };
+        let value =
+            CanonicalForm::new_from_moves(options(self.game.left()), options(self.game.right()));
+
+        self.previous[(self.current % buffer_len) as usize] = value.clone();
+        self.current += 1;
+
+        Some(value)
+    }
+}
+
+/// Arithmetic periodicity of a canonical form sequence, i.e. `G(n + period) = G(n) + saltus` for
+/// all `n >= preperiod`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Periodicity {
+    preperiod: usize,
+    period: usize,
+    saltus: CanonicalForm,
+}
+
+impl Periodicity {
+    /// Length of the irregular part before the sequence becomes periodic
+    #[inline]
+    pub const fn preperiod(&self) -> usize {
+        self.preperiod
+    }
+
+    /// Length of the period
+    #[inline]
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Difference between values that are one period apart
+    #[inline]
+    pub const fn saltus(&self) -> &CanonicalForm {
+        &self.saltus
+    }
+
+    /// Check if the periodicity is proven to hold for all heaps
+    #[inline]
+    pub fn is_proven(&self) -> bool {
+        self.saltus == CanonicalForm::new_integer(0)
+    }
+
+    /// Get the canonical form of any heap, given at least `preperiod + period` first values
+    pub fn value(&self, values: &[CanonicalForm], heap: usize) -> CanonicalForm {
+        if heap < self.preperiod + self.period {
+            return values[heap].clone();
+        }
+        let offset = heap - self.preperiod;
+        let periods = (offset / self.period) as i64;
+        &values[self.preperiod + offset % self.period]
+            + &(&CanonicalForm::new_integer(periods) * &self.saltus)
+    }
+}
+
+impl Display for Periodicity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "preperiod {}, period {}", self.preperiod, self.period)?;
+        if !self.is_proven() {
+            write!(f, ", saltus {}", self.saltus)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::transposition_table::ParallelTranspositionTable;
+
+    #[test]
+    fn sequence_matches_game_tree() {
+        let tt = ParallelTranspositionTable::new();
+        let game = PartizanSub::new(vec![1, 3], vec![2]);
+        for (heap, value) in game.clone().canonical_forms().take(20).enumerate() {
+            let position = game.clone().position(heap as u32);
+            assert_eq!(position.canonical_form(&tt), value, "{position}");
+        }
+    }
+
+    #[test]
+    fn correct_canonical_forms() {
+        let values = PartizanSub::new(vec![1], vec![2])
+            .canonical_forms()
+            .take(6)
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(values, ["0", "1", "{1|0}", "0", "1", "{1|0}"]);
+    }
+
+    #[test]
+    fn detects_period() {
+        // Impartial game
+        let periodicity = PartizanSub::new(vec![1, 2], vec![1, 2])
+            .detect_period(50)
+            .unwrap();
+        assert_eq!(periodicity.preperiod(), 0);
+        assert_eq!(periodicity.period(), 3);
+        assert!(periodicity.is_proven());
+
+        let periodicity = PartizanSub::new(vec![1], vec![2])
+            .detect_period(50)
+            .unwrap();
+        assert_eq!(periodicity.to_string(), "preperiod 0, period 3");
+
+        // Only Left can move
+        let periodicity = PartizanSub::new(vec![1], vec![]).detect_period(50).unwrap();
+        assert_eq!(periodicity.preperiod(), 0);
+        assert_eq!(periodicity.period(), 1);
+        assert_eq!(periodicity.saltus(), &CanonicalForm::new_integer(1));
+        assert!(!periodicity.is_proven());
+        assert_eq!(
+            periodicity.value(&[CanonicalForm::new_integer(0)], 42),
+            CanonicalForm::new_integer(42)
+        );
+
+        assert_eq!(
+            PartizanSub::new(vec![1, 2], vec![3]).to_string(),
+            "PartizanSub(1, 2 | 3)"
+        );
+        assert_eq!(
+            PartizanSub::new(vec![], vec![0]).detect_period(1),
+            Some(Periodicity {
+                preperiod: 0,
+                period: 1,
+                saltus: CanonicalForm::new_integer(0),
+            })
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! Push is played on a strip of squares with blue and red coins. In each turn player picks one of
+//! their coins and pushes it one square to the left. All coins directly to its left, up to the
+//! first empty square, are pushed as well. Coins pushed off the left end of the strip are removed.
+//!
+//! See also [`shove`](crate::short::partizan::games::shove), where every coin to the left is
+//! pushed.
+
+use crate::{
+    drawing::{self, BoundingBox, Canvas, Color, Draw},
+    short::partizan::{
+        Player,
+        partizan_game::PartizanGame,
+        strip::{Strip, StripTile},
+    },
+};
+use cgt_derive::Tile;
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// Tile of a Push or Shove strip
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Tile)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tile {
+    /// Empty square
+    #[tile(default, char('.'))]
+    Empty,
+
+    /// Left player's coin
+    #[tile(char('x'))]
+    Blue,
+
+    /// Right player's coin
+    #[tile(char('o'))]
+    Red,
+}
+
+impl Tile {
+    /// Get the player owning the coin, if any
+    #[inline]
+    pub const fn owner(self) -> Option<Player> {
+        match self {
+            Self::Empty => None,
+            Self::Blue => Some(Player::Left),
+            Self::Red => Some(Player::Right),
+        }
+    }
+}
+
+impl StripTile for Tile {
+    fn drawing_tile(self) -> drawing::Tile {
+        match self {
+            Self::Empty => drawing::Tile::Square {
+                color: Color::LIGHT_GRAY,
+            },
+            Self::Blue => drawing::Tile::Circle {
+                tile_color: Color::LIGHT_GRAY,
+                circle_color: Color::BLUE,
+            },
+            Self::Red => drawing::Tile::Circle {
+                tile_color: Color::LIGHT_GRAY,
+                circle_color: Color::RED,
+            },
+        }
+    }
+}
+
+/// Strip without trailing empty squares, as they are never reached by coins
+pub(crate) fn trimmed(strip: &Strip<Tile>) -> Strip<Tile> {
+    let len = strip
+        .tiles()
+        .iter()
+        .rposition(|tile| *tile != Tile::Empty)
+        .map_or(0, |last| last + 1);
+    Strip::new(strip.tiles()[..len].to_vec())
+}
+
+/// See [`push`](self) header
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Push {
+    strip: Strip<Tile>,
+}
+
+impl Push {
+    /// Create new Push position from a row of tiles
+    #[inline]
+    pub const fn new(tiles: Vec<Tile>) -> Self {
+        Self {
+            strip: Strip::new(tiles),
+        }
+    }
+
+    /// Get game row
+    #[inline]
+    pub const fn row(&self) -> &Vec<Tile> {
+        self.strip.tiles()
+    }
+
+    /// Output position as LaTeX `TikZ` picture
+    pub fn to_latex(&self) -> String {
+        self.strip.to_latex()
+    }
+
+    /// Get position after pushing coin at `idx` to the left. Note that it does not check if there
+    /// is a coin to push
+    #[must_use]
+    pub fn push(&self, idx: usize) -> Self {
+        let mut tiles = self.row().clone();
+        let start = tiles[..idx]
+            .iter()
+            .rposition(|tile| *tile == Tile::Empty)
+            .unwrap_or(0);
+        tiles[start..=idx].rotate_left(1);
+        tiles[idx] = Tile::Empty;
+        Self::new(tiles)
+    }
+
+    fn moves_for(&self, player: Player) -> Vec<Self> {
+        self.row()
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| tile.owner() == Some(player))
+            .map(|(idx, _)| self.push(idx))
+            .collect()
+    }
+}
+
+impl FromStr for Push {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Strip::from_str(s).map(|strip| Self { strip })
+    }
+}
+
+impl Display for Push {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.strip)
+    }
+}
+
+impl Draw for Push {
+    fn draw<C>(&self, canvas: &mut C)
+    where
+        C: Canvas,
+    {
+        self.strip.draw(canvas);
+    }
+
+    fn required_canvas<C>(&self) -> BoundingBox
+    where
+        C: Canvas,
+    {
+        self.strip.required_canvas::<C>()
+    }
+}
+
+impl PartizanGame for Push {
+    fn left_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Left)
+    }
+
+    fn right_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Right)
+    }
+
+    fn decompositions(&self) -> Vec<Self> {
+        vec![Self {
+            strip: trimmed(&self.strip),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::{
+        canonical_form::CanonicalForm, transposition_table::ParallelTranspositionTable,
+    };
+
+    macro_rules! push {
+        ($inp:expr) => {
+            Push::from_str($inp).expect("invalid row")
+        };
+    }
+
+    #[test]
+    fn moves() {
+        assert_eq!(
+            push!("x.ox").left_moves(),
+            vec![push!("..ox"), push!("xox.")]
+        );
+        assert_eq!(push!("x.ox").right_moves(), vec![push!("xo.x")]);
+        assert_eq!(
+            push!("xoxo").right_moves(),
+            vec![push!("o.xo"), push!("oxo.")]
+        );
+    }
+
+    #[test]
+    fn canonical_form() {
+        let tt = ParallelTranspositionTable::new();
+        for (position, value) in [
+            ("", "0"),
+            ("..x", "3"),
+            ("o.", "-1"),
+            ("xo", "-3/2"),
+            ("ox", "3/2"),
+        ] {
+            assert_eq!(
+                push!(position).canonical_form(&tt),
+                CanonicalForm::from_str(value).unwrap(),
+                "{position}"
+            );
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Shove is played on a strip of squares with blue and red coins. In each turn player picks one of
+//! their coins and pushes it one square to the left, together with all coins to its left. Coins
+//! pushed off the left end of the strip are removed.
+//!
+//! See also [`push`](crate::short::partizan::games::push), where only the adjacent coins are
+//! pushed.
+
+use crate::{
+    drawing::{BoundingBox, Canvas, Draw},
+    short::partizan::{
+        Player,
+        games::push::{self, Tile},
+        partizan_game::PartizanGame,
+        strip::Strip,
+    },
+};
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// See [`shove`](self) header
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Shove {
+    strip: Strip<Tile>,
+}
+
+impl Shove {
+    /// Create new Shove position from a row of tiles
+    #[inline]
+    pub const fn new(tiles: Vec<Tile>) -> Self {
+        Self {
+            strip: Strip::new(tiles),
+        }
+    }
+
+    /// Get game row
+    #[inline]
+    pub const fn row(&self) -> &Vec<Tile> {
+        self.strip.tiles()
+    }
+
+    /// Output position as LaTeX `TikZ` picture
+    pub fn to_latex(&self) -> String {
+        self.strip.to_latex()
+    }
+
+    /// Get position after shoving coin at `idx` to the left. Note that it does not check if there
+    /// is a coin to shove
+    #[must_use]
+    pub fn shove(&self, idx: usize) -> Self {
+        let mut tiles = self.row().clone();
+        tiles[..=idx].rotate_left(1);
+        tiles[idx] = Tile::Empty;
+        Self::new(tiles)
+    }
+
+    fn moves_for(&self, player: Player) -> Vec<Self> {
+        self.row()
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| tile.owner() == Some(player))
+            .map(|(idx, _)| self.shove(idx))
+            .collect()
+    }
+}
+
+impl FromStr for Shove {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Strip::from_str(s).map(|strip| Self { strip })
+    }
+}
+
+impl Display for Shove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.strip)
+    }
+}
+
+impl Draw for Shove {
+    fn draw<C>(&self, canvas: &mut C)
+    where
+        C: Canvas,
+    {
+        self.strip.draw(canvas);
+    }
+
+    fn required_canvas<C>(&self) -> BoundingBox
+    where
+        C: Canvas,
+    {
+        self.strip.required_canvas::<C>()
+    }
+}
+
+impl PartizanGame for Shove {
+    fn left_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Left)
+    }
+
+    fn right_moves(&self) -> Vec<Self> {
+        self.moves_for(Player::Right)
+    }
+
+    fn decompositions(&self) -> Vec<Self> {
+        vec![Self {
+            strip: push::trimmed(&self.strip),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::{
+        canonical_form::CanonicalForm, games::push::Push,
+        transposition_table::ParallelTranspositionTable,
+    };
+
+    macro_rules! shove {
+        ($inp:expr) => {
+            Shove::from_str($inp).expect("invalid row")
+        };
+    }
+
+    #[test]
+    fn moves() {
+        assert_eq!(
+            shove!("x.ox").left_moves(),
+            vec![shove!("..ox"), shove!(".ox.")]
+        );
+        assert_eq!(shove!("x.ox").right_moves(), vec![shove!(".o.x")]);
+    }
+
+    #[test]
+    fn canonical_form() {
+        let tt = ParallelTranspositionTable::new();
+        for (position, value) in [("", "0"), ("..x", "3"), ("xo", "-3/2"), ("x.o", "-5/2")] {
+            assert_eq!(
+                shove!(position).canonical_form(&tt),
+                CanonicalForm::from_str(value).unwrap(),
+                "{position}"
+            );
+        }
+
+        // Only adjacent coins are pushed in Push
+        let tt = ParallelTranspositionTable::new();
+        assert_eq!(
+            Push::from_str("x.o").unwrap().canonical_form(&tt),
+            CanonicalForm::new_integer(-2)
+        );
+    }
+}
@@ -11,6 +11,7 @@
 
 use crate::{
     drawing::{self, BoundingBox, Canvas, Color, Draw},
+    encoding::Encode,
     grid::{FiniteGrid, Grid, vec_grid::VecGrid},
     short::partizan::{canonical_form::CanonicalForm, partizan_game::PartizanGame},
 };
@@ -50,6 +51,19 @@ pub struct SkiJumps<G = VecGrid<Tile>> {
     grid: G,
 }
 
+impl<G> Encode for SkiJumps<G>
+where
+    G: Encode,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.grid.encode(buf);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        G::decode(input).map(|grid| Self { grid })
+    }
+}
+
 impl<G> Display for SkiJumps<G>
 where
     G: Grid<Item = Tile> + FiniteGrid,
@@ -7,8 +7,11 @@
 
 use crate::{
     drawing::{self, BoundingBox, Canvas, Color, Draw},
-    grid::{CharTile, FiniteGrid, Grid, vec_grid::VecGrid},
-    short::partizan::partizan_game::PartizanGame,
+    grid::vec_grid::VecGrid,
+    short::partizan::{
+        partizan_game::PartizanGame,
+        strip::{Strip, StripTile},
+    },
 };
 use cgt_derive::Tile;
 use std::{
@@ -33,37 +36,62 @@ pub enum Tile {
     Frog,
 }
 
+impl StripTile for Tile {
+    fn drawing_tile(self) -> drawing::Tile {
+        match self {
+            Self::Empty => drawing::Tile::Square {
+                color: Color::LIGHT_GRAY,
+            },
+            Self::Toad => drawing::Tile::Char {
+                tile_color: Color::LIGHT_GRAY,
+                text_color: Color::BLUE,
+                letter: 'T',
+            },
+            Self::Frog => drawing::Tile::Char {
+                tile_color: Color::LIGHT_GRAY,
+                text_color: Color::RED,
+                letter: 'F',
+            },
+        }
+    }
+}
+
 /// Singular row of the Toads and Frogs board
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ToadsAndFrogs {
-    tiles: Vec<Tile>,
+    strip: Strip<Tile>,
 }
 
 impl ToadsAndFrogs {
     /// Creates a new Toads and Frogs game from a row of tiles
     pub const fn new(tiles: Vec<Tile>) -> Self {
-        Self { tiles }
+        Self {
+            strip: Strip::new(tiles),
+        }
     }
 
     /// Get game row
     pub const fn row(&mut self) -> &Vec<Tile> {
-        &self.tiles
+        self.strip.tiles()
     }
 
     /// Get game row
     pub const fn row_mut(&mut self) -> &mut Vec<Tile> {
-        &mut self.tiles
+        self.strip.tiles_mut()
     }
 
     /// Construct grid equal to underlying game
-    pub fn grid(&self) -> VecGrid<Tile> {
-        // TODO: Use grid internally
-        let mut grid = VecGrid::filled(self.tiles.len() as u8, 1, Tile::Empty).unwrap();
-        for (i, t) in self.tiles.iter().copied().enumerate() {
-            grid.set(i as u8, 0, t);
-        }
-        grid
+    ///
+    /// # Errors
+    /// - Row has more than 255 tiles
+    pub fn grid(&self) -> Option<VecGrid<Tile>> {
+        self.strip.grid()
+    }
+
+    /// Output position as LaTeX `TikZ` picture
+    pub fn to_latex(&self) -> String {
+        self.strip.to_latex()
     }
 }
 
@@ -71,21 +99,13 @@ impl FromStr for ToadsAndFrogs {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut tiles = Vec::with_capacity(s.len());
-        for c in s.chars() {
-            tiles.push(Tile::char_to_tile(c).ok_or(())?);
-        }
-        Ok(Self::new(tiles))
+        Strip::from_str(s).map(|strip| Self { strip })
     }
 }
 
 impl Display for ToadsAndFrogs {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for tile in &self.tiles {
-            write!(f, "{}", tile.tile_to_char())?;
-        }
-
-        Ok(())
+        write!(f, "{}", self.strip)
     }
 }
 
@@ -94,28 +114,14 @@ impl Draw for ToadsAndFrogs {
     where
         C: Canvas,
     {
-        self.grid().draw(canvas, |tile| match tile {
-            Tile::Empty => drawing::Tile::Square {
-                color: Color::LIGHT_GRAY,
-            },
-            Tile::Toad => drawing::Tile::Char {
-                tile_color: Color::LIGHT_GRAY,
-                text_color: Color::BLUE,
-                letter: 'T',
-            },
-            Tile::Frog => drawing::Tile::Char {
-                tile_color: Color::LIGHT_GRAY,
-                text_color: Color::RED,
-                letter: 'F',
-            },
-        });
+        self.strip.draw(canvas);
     }
 
     fn required_canvas<C>(&self) -> BoundingBox
     where
         C: Canvas,
     {
-        self.grid().canvas_size::<C>()
+        self.strip.required_canvas::<C>()
     }
 }
 
@@ -124,20 +130,21 @@ impl PartizanGame for ToadsAndFrogs {
         let own = Tile::Toad;
         let opponent = Tile::Frog;
 
+        let tiles = self.strip.tiles();
         let mut moves = Vec::new();
 
-        for (idx, tile) in self.tiles.iter().copied().enumerate() {
+        for (idx, tile) in tiles.iter().copied().enumerate() {
             if tile == own {
-                if idx < self.tiles.len() - 1 && self.tiles[idx + 1] == Tile::Empty {
-                    let mut new_tiles = self.tiles.clone();
+                if idx < tiles.len() - 1 && tiles[idx + 1] == Tile::Empty {
+                    let mut new_tiles = tiles.clone();
                     new_tiles[idx] = Tile::Empty;
                     new_tiles[idx + 1] = own;
                     moves.push(Self::new(new_tiles));
-                } else if idx + 2 < self.tiles.len()
-                    && self.tiles[idx + 1] == opponent
-                    && self.tiles[idx + 2] == Tile::Empty
+                } else if idx + 2 < tiles.len()
+                    && tiles[idx + 1] == opponent
+                    && tiles[idx + 2] == Tile::Empty
                 {
-                    let mut new_tiles = self.tiles.clone();
+                    let mut new_tiles = tiles.clone();
                     new_tiles[idx] = Tile::Empty;
                     new_tiles[idx + 2] = own;
                     moves.push(Self::new(new_tiles));
@@ -152,20 +159,18 @@ impl PartizanGame for ToadsAndFrogs {
         let own = Tile::Frog;
         let opponent = Tile::Toad;
 
+        let tiles = self.strip.tiles();
         let mut moves = Vec::new();
 
-        for (idx, tile) in self.tiles.iter().copied().enumerate() {
+        for (idx, tile) in tiles.iter().copied().enumerate() {
             if tile == own {
-                if idx > 0 && self.tiles[idx - 1] == Tile::Empty {
-                    let mut new_tiles = self.tiles.clone();
+                if idx > 0 && tiles[idx - 1] == Tile::Empty {
+                    let mut new_tiles = tiles.clone();
                     new_tiles[idx] = Tile::Empty;
                     new_tiles[idx - 1] = own;
                     moves.push(Self::new(new_tiles));
-                } else if idx > 1
-                    && self.tiles[idx - 1] == opponent
-                    && self.tiles[idx - 2] == Tile::Empty
-                {
-                    let mut new_tiles = self.tiles.clone();
+                } else if idx > 1 && tiles[idx - 1] == opponent && tiles[idx - 2] == Tile::Empty {
+                    let mut new_tiles = tiles.clone();
                     new_tiles[idx] = Tile::Empty;
                     new_tiles[idx - 2] = own;
                     moves.push(Self::new(new_tiles));
@@ -1,83 +1,137 @@
 //! Toppling Dominoes is played on a multiple rows of dominoes where each player in their turns
 //! chooses one of the dominoes of their color and topples it to the left (or right) removing it
-//! and all other dominoes to the left (or right) of it.
+//! and all other dominoes to the left (or right) of it. Green dominoes can be toppled by both
+//! players.
 
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 use crate::{
-    grid::{CharTile, FiniteGrid, Grid, small_bit_grid::SmallBitGrid},
-    short::partizan::partizan_game::PartizanGame,
+    drawing::{self, BoundingBox, Canvas, Color, Draw},
+    encoding::Encode,
+    short::partizan::{
+        Player,
+        partizan_game::PartizanGame,
+        strip::{self, Strip, StripTile},
+    },
 };
 use cgt_derive::Tile;
 
 /// Color of dominoes
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Tile)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Tile {
     /// Blue domino
-    #[tile(char('x'), bool(true))]
+    #[tile(char('x'))]
     Blue,
 
     /// Red domino
-    #[tile(char('o'), bool(false))]
+    #[tile(char('o'))]
     Red,
+
+    /// Green domino
+    #[tile(char('g'))]
+    Green,
+}
+
+impl Tile {
+    /// Check if player can topple the domino
+    #[inline]
+    pub const fn can_topple(self, player: Player) -> bool {
+        matches!(
+            (self, player),
+            (Self::Green, _) | (Self::Blue, Player::Left) | (Self::Red, Player::Right)
+        )
+    }
+}
+
+impl StripTile for Tile {
+    fn drawing_tile(self) -> drawing::Tile {
+        let color = match self {
+            Self::Blue => Color::BLUE,
+            Self::Red => Color::RED,
+            Self::Green => Color::GREEN,
+        };
+        drawing::Tile::Square { color }
+    }
 }
 
 /// Game of Toppling Dominoes
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TopplingDominoes {
-    rows: Vec<SmallBitGrid<Tile>>,
+    rows: Vec<Strip<Tile>>,
+}
+
+impl Encode for TopplingDominoes {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.rows.encode(buf);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        Vec::decode(input).map(|rows| Self { rows })
+    }
 }
 
 impl Display for TopplingDominoes {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.rows.len() {
-            let row = &self.rows[y];
-            for x in 0..row.width() {
-                write!(f, "{}", row.get(x, 0).tile_to_char())?;
-            }
-            if y != self.rows.len() - 1 {
-                write!(f, "|")?;
-            }
-        }
-        Ok(())
+        strip::display_rows(f, &self.rows)
+    }
+}
+
+impl FromStr for TopplingDominoes {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        strip::parse_rows(s).map(Self::new).ok_or(())
+    }
+}
+
+impl Draw for TopplingDominoes {
+    fn draw<C>(&self, canvas: &mut C)
+    where
+        C: Canvas,
+    {
+        strip::draw_rows(&self.rows, canvas);
+    }
+
+    fn required_canvas<C>(&self) -> BoundingBox
+    where
+        C: Canvas,
+    {
+        strip::rows_canvas::<Tile, C>(&self.rows)
     }
 }
 
 impl TopplingDominoes {
     /// Create new Toppling Dominoes game from a vector of rows
     #[inline]
-    pub const fn new(rows: Vec<SmallBitGrid<Tile>>) -> Self {
+    pub const fn new(rows: Vec<Strip<Tile>>) -> Self {
         Self { rows }
     }
 
-    fn moves_for(&self, own_tile: Tile) -> Vec<Self> {
-        let mut moves = Vec::with_capacity(
-            2 * self
-                .rows
-                .iter()
-                .map(|row| {
-                    let mut acc = 0;
-                    for x in 0..row.width() {
-                        acc += (row.get(x, 0) == own_tile) as usize;
-                    }
-                    acc
-                })
-                .sum::<usize>(),
-        );
+    /// Get rows of dominoes
+    #[inline]
+    pub fn rows(&self) -> &[Strip<Tile>] {
+        &self.rows
+    }
+
+    /// Output position as LaTeX `TikZ` picture
+    pub fn to_latex(&self) -> String {
+        strip::rows_to_latex(&self.rows)
+    }
+
+    fn moves_for(&self, player: Player) -> Vec<Self> {
+        let mut moves = Vec::new();
 
         for (row_idx, row) in self.rows.iter().enumerate() {
-            for x in 0..row.width() {
-                if row.get(x, 0) == own_tile {
+            for (x, tile) in row.tiles().iter().enumerate() {
+                if tile.can_topple(player) {
                     let mut right = self.clone();
-                    right.rows[row_idx].width = x;
+                    right.rows[row_idx].tiles_mut().truncate(x);
                     moves.push(right);
 
                     let mut left = self.clone();
-                    left.rows[row_idx].width -= x + 1;
-                    for n in 0..left.rows[row_idx].width {
-                        left.rows[row_idx].set(n, 0, row.get(n + x + 1, 0));
-                    }
+                    left.rows[row_idx].tiles_mut().drain(..=x);
                     moves.push(left);
                 }
             }
@@ -90,18 +144,18 @@ impl TopplingDominoes {
 impl PartizanGame for TopplingDominoes {
     #[inline]
     fn left_moves(&self) -> Vec<Self> {
-        self.moves_for(Tile::Blue)
+        self.moves_for(Player::Left)
     }
 
     #[inline]
     fn right_moves(&self) -> Vec<Self> {
-        self.moves_for(Tile::Red)
+        self.moves_for(Player::Right)
     }
 
     fn decompositions(&self) -> Vec<Self> {
         let mut decompositions = Vec::with_capacity(self.rows.len());
         for row in &self.rows {
-            decompositions.push(Self::new(vec![*row]));
+            decompositions.push(Self::new(vec![row.clone()]));
         }
         decompositions
     }
@@ -109,14 +163,13 @@ impl PartizanGame for TopplingDominoes {
 
 #[test]
 fn correct_left() {
-    let td = TopplingDominoes::new(vec![
-        SmallBitGrid::from_arr(
-            5,
-            1,
-            &[Tile::Blue, Tile::Red, Tile::Red, Tile::Blue, Tile::Blue],
-        )
-        .unwrap(),
-    ]);
+    let td = TopplingDominoes::new(vec![Strip::new(vec![
+        Tile::Blue,
+        Tile::Red,
+        Tile::Red,
+        Tile::Blue,
+        Tile::Blue,
+    ])]);
     assert_eq!(
         td.left_moves()
             .iter()
@@ -131,14 +184,13 @@ fn correct_left() {
 
 #[test]
 fn correct_right() {
-    let td = TopplingDominoes::new(vec![
-        SmallBitGrid::from_arr(
-            5,
-            1,
-            &[Tile::Blue, Tile::Red, Tile::Red, Tile::Blue, Tile::Blue],
-        )
-        .unwrap(),
-    ]);
+    let td = TopplingDominoes::new(vec![Strip::new(vec![
+        Tile::Blue,
+        Tile::Red,
+        Tile::Red,
+        Tile::Blue,
+        Tile::Blue,
+    ])]);
     assert_eq!(
         td.right_moves()
             .iter()
@@ -155,14 +207,41 @@ fn correct_right() {
 fn correct_canonical() {
     use crate::short::partizan::transposition_table::ParallelTranspositionTable;
 
-    let td = TopplingDominoes::new(vec![
-        SmallBitGrid::from_arr(
-            5,
-            1,
-            &[Tile::Blue, Tile::Red, Tile::Red, Tile::Blue, Tile::Blue],
-        )
-        .unwrap(),
-    ]);
+    let td = TopplingDominoes::new(vec![Strip::new(vec![
+        Tile::Blue,
+        Tile::Red,
+        Tile::Red,
+        Tile::Blue,
+        Tile::Blue,
+    ])]);
     let tt = ParallelTranspositionTable::new();
     assert_eq!(td.canonical_form(&tt).to_string(), "{1|*}");
 }
+
+#[test]
+fn green_dominoes() {
+    use crate::short::partizan::transposition_table::ParallelTranspositionTable;
+
+    let tt = ParallelTranspositionTable::new();
+    let value = |position: &str| {
+        TopplingDominoes::from_str(position)
+            .unwrap()
+            .canonical_form(&tt)
+            .to_string()
+    };
+
+    // Row of green dominoes is a nim heap
+    assert_eq!(value("ggg"), "*3");
+    assert_eq!(value("xg"), "{1|0}");
+    assert_eq!(value("xo"), "*");
+    assert_eq!(value("xo|g"), "0");
+}
+
+#[test]
+fn parse_display_roundtrip() {
+    for position in ["xog|ox", "", "|g|"] {
+        let td = TopplingDominoes::from_str(position).unwrap();
+        assert_eq!(td.to_string(), position);
+    }
+    assert!(TopplingDominoes::from_str("x.o").is_err());
+}
@@ -0,0 +1,436 @@
+//! Outcome classes of short partizan games and solver that finds them without computing the full
+//! canonical form
+
+use crate::{
+    short::partizan::{
+        Player, canonical_form::CanonicalForm, partizan_game::PartizanGame,
+        transposition_table::TranspositionTable,
+    },
+    total::TotalWrapper,
+};
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Display},
+};
+
+/// Outcome class of a game under normal play
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Outcome {
+    /// Left wins, regardless of who starts
+    L,
+
+    /// First player wins
+    N,
+
+    /// Second player wins
+    P,
+
+    /// Right wins, regardless of who starts
+    R,
+}
+
+impl Outcome {
+    /// Construct outcome from results of games where each player moves first
+    pub const fn from_first_player_wins(left_first_wins: bool, right_first_wins: bool) -> Self {
+        match (left_first_wins, right_first_wins) {
+            (true, true) => Outcome::N,
+            (true, false) => Outcome::L,
+            (false, true) => Outcome::R,
+            (false, false) => Outcome::P,
+        }
+    }
+
+    /// Check if the player wins when moving first
+    pub const fn wins_moving_first(self, player: Player) -> bool {
+        matches!(
+            (self, player),
+            (Outcome::N, _) | (Outcome::L, Player::Left) | (Outcome::R, Player::Right)
+        )
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::L => write!(f, "L"),
+            Outcome::N => write!(f, "N"),
+            Outcome::P => write!(f, "P"),
+            Outcome::R => write!(f, "R"),
+        }
+    }
+}
+
+/// Sum of components that are searched explicitly, and sum of components with known values
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Position<G> {
+    components: Vec<G>,
+    known: TotalWrapper<CanonicalForm>,
+}
+
+/// Outcome solver using boolean negamax search or proof-number search, with its own win/loss
+/// transposition table.
+///
+/// Positions are split into components using [`PartizanGame::decompositions`]. Components whose
+/// canonical form is stored in the given transposition table, or that are small enough, are
+/// replaced by their canonical forms and their sum is played as a single component.
+pub struct OutcomeSolver<'tt, G, TT> {
+    transposition_table: &'tt TT,
+    is_small: Option<fn(&G) -> bool>,
+    wins: HashMap<(Position<G>, Player), bool, ahash::RandomState>,
+}
+
+impl<G, TT> Debug for OutcomeSolver<'_, G, TT>
+where
+    G: Debug,
+    TT: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OutcomeSolver")
+            .field("transposition_table", &self.transposition_table)
+            .field("is_small", &self.is_small)
+            .field("solved", &self.wins.len())
+            .finish()
+    }
+}
+
+impl<'tt, G, TT> OutcomeSolver<'tt, G, TT>
+where
+    G: PartizanGame,
+    TT: TranspositionTable<G> + Sync,
+{
+    /// Create new solver that looks up values of components in the given transposition table
+    pub fn new(transposition_table: &'tt TT) -> Self {
+        Self {
+            transposition_table,
+            is_small: None,
+            wins: HashMap::default(),
+        }
+    }
+
+    /// Compute canonical forms of components for which `is_small` returns `true`, instead of
+    /// searching them
+    #[must_use]
+    pub fn with_small_components(mut self, is_small: fn(&G) -> bool) -> Self {
+        self.is_small = Some(is_small);
+        self
+    }
+
+    /// Number of positions with known winner stored by the solver
+    pub fn solved_positions(&self) -> usize {
+        self.wins.len()
+    }
+
+    /// Get the outcome class of a position using negamax search
+    pub fn outcome(&mut self, position: &G) -> Outcome {
+        let position = self.split(
+            std::slice::from_ref(position),
+            CanonicalForm::new_integer(0),
+        );
+        Outcome::from_first_player_wins(
+            self.wins(&position, Player::Left),
+            self.wins(&position, Player::Right),
+        )
+    }
+
+    /// Get the outcome class of a position using proof-number search. Returns `None` if the
+    /// outcome could not be proven after creating `max_nodes` nodes for each player.
+    pub fn proof_number_outcome(&mut self, position: &G, max_nodes: usize) -> Option<Outcome> {
+        let position = self.split(
+            std::slice::from_ref(position),
+            CanonicalForm::new_integer(0),
+        );
+        Some(Outcome::from_first_player_wins(
+            self.proof_number_search(&position, Player::Left, max_nodes)?,
+            self.proof_number_search(&position, Player::Right, max_nodes)?,
+        ))
+    }
+
+    /// Split components into decompositions and move those with known values to the known part
+    fn split(&self, components: &[G], mut known: CanonicalForm) -> Position<G> {
+        let mut unknown = Vec::with_capacity(components.len());
+        for component in components.iter().flat_map(PartizanGame::decompositions) {
+            if let Some(value) = self.transposition_table.lookup_position(&component) {
+                known += value;
+            } else if self.is_small.is_some_and(|is_small| is_small(&component)) {
+                known += component.canonical_form(self.transposition_table);
+            } else {
+                unknown.push(component);
+            }
+        }
+        Position {
+            components: unknown,
+            known: TotalWrapper::new(known),
+        }
+    }
+
+    /// Result of the game when only the known part is left
+    fn known_wins(position: &Position<G>, player: Player) -> Option<bool> {
+        position
+            .components
+            .is_empty()
+            .then(|| position.known.outcome().wins_moving_first(player))
+    }
+
+    fn moves(&self, position: &Position<G>, player: Player) -> Vec<Position<G>> {
+        let mut moves = Vec::new();
+        for (idx, component) in position.components.iter().enumerate() {
+            let component_moves = match player {
+                Player::Left => component.left_moves(),
+                Player::Right => component.right_moves(),
+            };
+            for component_move in component_moves {
+                let mut components = position.components.clone();
+                components.swap_remove(idx);
+                let mut new_position = self.split(&[component_move], (*position.known).clone());
+                new_position.components.extend(components);
+                moves.push(new_position);
+            }
+        }
+
+        let known_moves = match player {
+            Player::Left => position.known.left_moves().collect::<Vec<_>>(),
+            Player::Right => position.known.right_moves().collect::<Vec<_>>(),
+        };
+        for known_move in known_moves {
+            moves.push(Position {
+                components: position.components.clone(),
+                known: TotalWrapper::new(known_move.into_owned()),
+            });
+        }
+        moves
+    }
+
+    fn move_count(position: &Position<G>, player: Player) -> usize {
+        let known = match player {
+            Player::Left => position.known.left_moves().len(),
+            Player::Right => position.known.right_moves().len(),
+        };
+        position
+            .components
+            .iter()
+            .map(|component| match player {
+                Player::Left => component.left_moves().len(),
+                Player::Right => component.right_moves().len(),
+            })
+            .sum::<usize>()
+            + known
+    }
+
+    /// Check if the player wins moving first
+    fn wins(&mut self, position: &Position<G>, player: Player) -> bool {
+        if let Some(wins) = Self::known_wins(position, player) {
+            return wins;
+        }
+        if let Some(&wins) = self.wins.get(&(position.clone(), player)) {
+            return wins;
+        }
+
+        let opponent = player.opposite();
+        let mut moves = self.moves(position, player);
+
+        // Enhanced transposition cutoff: check if any move is already known to be winning
+        let known_win = moves.iter().any(|m| {
+            Self::known_wins(m, opponent).or_else(|| self.wins.get(&(m.clone(), opponent)).copied())
+                == Some(false)
+        });
+
+        // Try moves that leave the opponent with fewest replies first
+        let result = known_win || {
+            moves.sort_by_cached_key(|m| Self::move_count(m, opponent));
+            moves.iter().any(|m| !self.wins(m, opponent))
+        };
+        self.wins.insert((position.clone(), player), result);
+        result
+    }
+
+    /// Check if the player wins moving first using proof-number search
+    fn proof_number_search(
+        &mut self,
+        position: &Position<G>,
+        attacker: Player,
+        max_nodes: usize,
+    ) -> Option<bool> {
+        let mut nodes = vec![self.new_node(position.clone(), attacker, attacker)];
+
+        let mut path = Vec::new();
+        while nodes[0].proof != 0 && nodes[0].disproof != 0 && nodes.len() < max_nodes {
+            // Select the most proving node
+            path.clear();
+            let mut current = 0;
+            while !nodes[current].children.is_empty() {
+                path.push(current);
+                let is_or = nodes[current].to_move == attacker;
+                current = *nodes[current]
+                    .children
+                    .iter()
+                    .min_by_key(|&&child| {
+                        if is_or {
+                            nodes[child].proof
+                        } else {
+                            nodes[child].disproof
+                        }
+                    })
+                    .expect("node has children");
+            }
+
+            // Expand it
+            let to_move = nodes[current].to_move;
+            for child in self.moves(&nodes[current].position, to_move) {
+                let child = self.new_node(child, to_move.opposite(), attacker);
+                nodes.push(child);
+                let child = nodes.len() - 1;
+                nodes[current].children.push(child);
+            }
+            path.push(current);
+
+            // Update proof and disproof numbers of ancestors
+            for &idx in path.iter().rev() {
+                let node = &nodes[idx];
+                let (proof, disproof) = if node.children.is_empty() {
+                    // Player to move has no moves and loses
+                    if node.to_move == attacker {
+                        (PN_INFINITY, 0)
+                    } else {
+                        (0, PN_INFINITY)
+                    }
+                } else {
+                    let proofs = node.children.iter().map(|&child| nodes[child].proof);
+                    let disproofs = node.children.iter().map(|&child| nodes[child].disproof);
+                    if node.to_move == attacker {
+                        (proofs.min().unwrap(), pn_sum(disproofs))
+                    } else {
+                        (pn_sum(proofs), disproofs.min().unwrap())
+                    }
+                };
+                nodes[idx].proof = proof;
+                nodes[idx].disproof = disproof;
+            }
+        }
+
+        // Remember all solved positions
+        for node in &nodes {
+            if node.proof == 0 || node.disproof == 0 {
+                let attacker_wins = node.proof == 0;
+                let wins = attacker_wins == (node.to_move == attacker);
+                self.wins
+                    .entry((node.position.clone(), node.to_move))
+                    .or_insert(wins);
+            }
+        }
+
+        if nodes[0].proof == 0 {
+            Some(true)
+        } else if nodes[0].disproof == 0 {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    fn new_node(&self, position: Position<G>, to_move: Player, attacker: Player) -> ProofNode<G> {
+        let solved = Self::known_wins(&position, to_move)
+            .or_else(|| self.wins.get(&(position.clone(), to_move)).copied());
+        let (proof, disproof) = match solved {
+            Some(wins) if wins == (to_move == attacker) => (0, PN_INFINITY),
+            Some(_) => (PN_INFINITY, 0),
+            // Initialize with mobility, so nodes with fewer moves are easier to (dis)prove
+            None => {
+                let moves = Self::move_count(&position, to_move) as u64;
+                if to_move == attacker {
+                    (1, moves.max(1))
+                } else {
+                    (moves.max(1), 1)
+                }
+            }
+        };
+        ProofNode {
+            position,
+            to_move,
+            proof,
+            disproof,
+            children: Vec::new(),
+        }
+    }
+}
+
+/// Proof or disproof number of a node that cannot be (dis)proven
+const PN_INFINITY: u64 = u64::MAX;
+
+fn pn_sum(numbers: impl Iterator<Item = u64>) -> u64 {
+    numbers.fold(0, u64::saturating_add)
+}
+
+struct ProofNode<G> {
+    position: Position<G>,
+    to_move: Player,
+    proof: u64,
+    disproof: u64,
+    children: Vec<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        grid::{FiniteGrid, small_bit_grid::SmallBitGrid},
+        short::partizan::{
+            games::domineering::Domineering,
+            transposition_table::{NoTranspositionTable, ParallelTranspositionTable},
+        },
+    };
+    use std::str::FromStr;
+
+    fn positions() -> impl Iterator<Item = Domineering> {
+        (0..1 << 9)
+            .map(|id| Domineering::new(SmallBitGrid::from_number(3, 3, id).unwrap()))
+            .chain(
+                (0..1 << 8)
+                    .map(|id| Domineering::new(SmallBitGrid::from_number(4, 2, id).unwrap())),
+            )
+    }
+
+    #[test]
+    fn negamax_agrees_with_canonical_form() {
+        let tt = ParallelTranspositionTable::new();
+        for position in positions() {
+            let expected = position.canonical_form(&tt).outcome();
+            let no_tt = NoTranspositionTable::new();
+            let mut solver = OutcomeSolver::new(&no_tt);
+            assert_eq!(solver.outcome(&position), expected, "{position}");
+        }
+    }
+
+    #[test]
+    fn uses_known_components() {
+        let tt = ParallelTranspositionTable::new();
+        let mut solver = OutcomeSolver::new(&tt)
+            .with_small_components(|position: &Domineering| position.grid().width() <= 2);
+        for position in positions() {
+            let expected = position
+                .canonical_form(&NoTranspositionTable::new())
+                .outcome();
+            assert_eq!(solver.outcome(&position), expected, "{position}");
+        }
+    }
+
+    #[test]
+    fn proof_number_search_agrees_with_canonical_form() {
+        let tt = ParallelTranspositionTable::new();
+        for position in positions() {
+            let expected = position.canonical_form(&tt).outcome();
+            let no_tt = NoTranspositionTable::new();
+            let mut solver = OutcomeSolver::new(&no_tt);
+            assert_eq!(
+                solver.proof_number_outcome(&position, 100_000),
+                Some(expected),
+                "{position}"
+            );
+        }
+
+        let position: Domineering = Domineering::from_str("......|......|......|......").unwrap();
+        let no_tt = NoTranspositionTable::new();
+        let mut solver = OutcomeSolver::new(&no_tt);
+        assert_eq!(solver.proof_number_outcome(&position, 10), None);
+    }
+}
@@ -3,8 +3,10 @@
 use crate::{
     numeric::rational::Rational,
     short::partizan::{
-        canonical_form::CanonicalForm, thermograph::Thermograph,
-        transposition_table::TranspositionTable,
+        canonical_form::CanonicalForm,
+        outcome::{Outcome, OutcomeSolver},
+        thermograph::Thermograph,
+        transposition_table::{NoTranspositionTable, TranspositionTable},
     },
 };
 use std::hash::Hash;
@@ -106,6 +108,12 @@ pub trait PartizanGame: Sized + Clone + Hash + Send + Sync + Eq {
         result
     }
 
+    /// Get the outcome class of the game position, without computing its canonical form.
+    /// See [`OutcomeSolver`] for more options.
+    fn outcome(&self) -> Outcome {
+        OutcomeSolver::new(&NoTranspositionTable::new()).outcome(self)
+    }
+
     // TODO: Find a way to reduce duplication - maybe macro?
 
     /// List of canonical moves for the Left player
@@ -0,0 +1,59 @@
+//! Tiles of grid games in which every piece on the board belongs to one of the players, like
+//! stones in Clobber or cherries in Cherries
+
+use crate::{
+    drawing::{self, Color},
+    short::partizan::Player,
+};
+use cgt_derive::Tile;
+
+/// Tile that is empty or holds a blue or red piece
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Tile)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Stone {
+    /// Empty tile without a piece
+    #[tile(char('.'), default)]
+    Empty,
+
+    /// Blue piece, owned by Left
+    #[tile(char('x'))]
+    Left,
+
+    /// Red piece, owned by Right
+    #[tile(char('o'))]
+    Right,
+}
+
+impl Stone {
+    /// Check if the tile holds a piece of any player
+    #[inline]
+    pub fn is_occupied(self) -> bool {
+        self != Self::Empty
+    }
+
+    /// Get the way the tile is drawn
+    pub const fn drawing_tile(self) -> drawing::Tile {
+        match self {
+            Self::Empty => drawing::Tile::Square {
+                color: Color::LIGHT_GRAY,
+            },
+            Self::Left => drawing::Tile::Circle {
+                tile_color: Color::LIGHT_GRAY,
+                circle_color: Color::BLUE,
+            },
+            Self::Right => drawing::Tile::Circle {
+                tile_color: Color::LIGHT_GRAY,
+                circle_color: Color::RED,
+            },
+        }
+    }
+}
+
+impl From<Player> for Stone {
+    fn from(player: Player) -> Self {
+        match player {
+            Player::Left => Self::Left,
+            Player::Right => Self::Right,
+        }
+    }
+}
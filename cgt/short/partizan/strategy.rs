@@ -0,0 +1,378 @@
+//! Playing sums of partizan games - finding winning moves and, when there are none, good moves
+//! using thermograph-based heuristics
+
+use crate::{
+    numeric::rational::Rational,
+    short::partizan::{
+        Player,
+        canonical_form::{CanonicalForm, orthodox_moves},
+        partizan_game::PartizanGame,
+        thermograph::Thermograph,
+        transposition_table::TranspositionTable,
+    },
+};
+
+/// Move in a sum of games
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SumMove<G> {
+    /// Index of the component where the move is made
+    pub component: usize,
+
+    /// Component position after the move
+    pub position: G,
+}
+
+/// Heuristic strategy used when there is no winning move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Heuristic {
+    /// Play orthodox move in the hottest component
+    Hotstrat,
+
+    /// Answer in the component where the opponent has just moved, if it became hotter than the
+    /// rest of the sum. Otherwise play [`Heuristic::Thermostrat`].
+    Sentestrat {
+        /// Component where the opponent has made the last move
+        last_component: Option<usize>,
+    },
+
+    /// Find the temperature that is best for the player in the compound thermograph, and play
+    /// orthodox move in the widest component at that temperature
+    Thermostrat,
+}
+
+/// Strategy for playing sums of game positions, using canonical forms of components
+#[derive(Debug)]
+pub struct Strategy<'tt, TT> {
+    transposition_table: &'tt TT,
+}
+
+impl<'tt, TT> Strategy<'tt, TT> {
+    /// Create new strategy that stores canonical forms in the given transposition table
+    pub const fn new(transposition_table: &'tt TT) -> Self {
+        Self {
+            transposition_table,
+        }
+    }
+
+    /// Get all moves after which the player wins, assuming perfect play
+    pub fn winning_moves<G>(&self, components: &[G], player: Player) -> Vec<SumMove<G>>
+    where
+        G: PartizanGame,
+        TT: TranspositionTable<G> + Sync,
+    {
+        let values = self.values(components);
+        let total = values.iter().sum::<CanonicalForm>();
+        let zero = CanonicalForm::new_integer(0);
+
+        let mut winning = Vec::new();
+        for (component, (position, value)) in components.iter().zip(&values).enumerate() {
+            let rest = &total - value;
+            for position in player_moves(position, player) {
+                let after = &rest + position.canonical_form(self.transposition_table);
+                let wins = match player {
+                    Player::Left => CanonicalForm::leq(&zero, &after),
+                    Player::Right => CanonicalForm::leq(&after, &zero),
+                };
+                if wins {
+                    winning.push(SumMove {
+                        component,
+                        position,
+                    });
+                }
+            }
+        }
+        winning
+    }
+
+    /// Get a winning move if there is one, otherwise a move chosen by the heuristic. Returns
+    /// `None` if the player has no moves.
+    pub fn best_move<G>(
+        &self,
+        components: &[G],
+        player: Player,
+        heuristic: Heuristic,
+    ) -> Option<SumMove<G>>
+    where
+        G: PartizanGame,
+        TT: TranspositionTable<G> + Sync,
+    {
+        self.winning_moves(components, player)
+            .into_iter()
+            .next()
+            .or_else(|| self.heuristic_move(components, player, heuristic))
+    }
+
+    /// Get a move chosen by the heuristic. Returns `None` if the player has no moves.
+    pub fn heuristic_move<G>(
+        &self,
+        components: &[G],
+        player: Player,
+        heuristic: Heuristic,
+    ) -> Option<SumMove<G>>
+    where
+        G: PartizanGame,
+        TT: TranspositionTable<G> + Sync,
+    {
+        let thermographs = self
+            .values(components)
+            .iter()
+            .map(CanonicalForm::thermograph)
+            .collect::<Vec<_>>();
+        let playable =
+            |component: &usize| !player_moves(&components[*component], player).is_empty();
+
+        let (component, temperature) = match heuristic {
+            Heuristic::Hotstrat => hottest(&thermographs, playable)?,
+            Heuristic::Sentestrat {
+                last_component: Some(last_component),
+            } if last_component < components.len() && playable(&last_component) => {
+                let ambient = hottest(&thermographs, |component| *component != last_component)
+                    .map_or_else(|| Rational::from(-1), |(_, temperature)| temperature);
+                let temperature = thermographs[last_component].temperature().to_rational();
+                if temperature > ambient {
+                    (last_component, ambient.max(Rational::from(0)))
+                } else {
+                    thermostrat(&thermographs, player, playable)?
+                }
+            }
+            Heuristic::Sentestrat { .. } | Heuristic::Thermostrat => {
+                thermostrat(&thermographs, player, playable)?
+            }
+        };
+
+        self.orthodox_move(&components[component], player, temperature)
+            .map(|position| SumMove {
+                component,
+                position,
+            })
+    }
+
+    /// Move in a component that is best for the player at the given ambient temperature
+    fn orthodox_move<G>(&self, position: &G, player: Player, temperature: Rational) -> Option<G>
+    where
+        G: PartizanGame,
+        TT: TranspositionTable<G> + Sync,
+    {
+        let moves = player_moves(position, player).into_iter().map(|position| {
+            let thermograph = position
+                .canonical_form(self.transposition_table)
+                .thermograph();
+            (position, thermograph)
+        });
+        orthodox_moves(moves, player, temperature)
+            .into_iter()
+            .next()
+    }
+
+    fn values<G>(&self, components: &[G]) -> Vec<CanonicalForm>
+    where
+        G: PartizanGame,
+        TT: TranspositionTable<G> + Sync,
+    {
+        components
+            .iter()
+            .map(|component| component.canonical_form(self.transposition_table))
+            .collect()
+    }
+}
+
+fn player_moves<G>(position: &G, player: Player) -> Vec<G>
+where
+    G: PartizanGame,
+{
+    match player {
+        Player::Left => position.left_moves(),
+        Player::Right => position.right_moves(),
+    }
+}
+
+/// Hottest component among those satisfying the predicate, and its temperature
+fn hottest(
+    thermographs: &[Thermograph],
+    mut predicate: impl FnMut(&usize) -> bool,
+) -> Option<(usize, Rational)> {
+    (0..thermographs.len())
+        .filter(|component| predicate(component))
+        .map(|component| {
+            (
+                component,
+                thermographs[component].temperature().to_rational(),
+            )
+        })
+        .reduce(|best, current| if current.1 > best.1 { current } else { best })
+}
+
+/// Width of the thermograph at the given temperature
+fn width_at(thermograph: &Thermograph, temperature: Rational) -> Rational {
+    thermograph.left_wall.value_at(temperature) - thermograph.right_wall.value_at(temperature)
+}
+
+/// Choose component and temperature using the compound thermograph. For Left the compound
+/// thermograph is bounded by the sum of right walls plus the largest width, and Left picks the
+/// temperature where it is the largest. Right proceeds symmetrically.
+fn thermostrat(
+    thermographs: &[Thermograph],
+    player: Player,
+    mut playable: impl FnMut(&usize) -> bool,
+) -> Option<(usize, Rational)> {
+    let playable = (0..thermographs.len())
+        .filter(|component| playable(component))
+        .collect::<Vec<_>>();
+    if playable.is_empty() {
+        return None;
+    }
+
+    // Compound thermograph is piecewise linear, so it is enough to check critical points
+    let mut temperatures = thermographs
+        .iter()
+        .flat_map(|thermograph| {
+            thermograph
+                .left_wall
+                .critical_points
+                .iter()
+                .chain(&thermograph.right_wall.critical_points)
+        })
+        .copied()
+        .filter(|temperature| !temperature.is_infinite() && *temperature >= Rational::from(0))
+        .collect::<Vec<_>>();
+    temperatures.push(Rational::from(0));
+    temperatures.sort();
+    temperatures.dedup();
+
+    let compound_at = |temperature: Rational| {
+        let max_width = playable
+            .iter()
+            .map(|&component| width_at(&thermographs[component], temperature))
+            .max()
+            .unwrap_or_else(|| Rational::from(0));
+        match player {
+            Player::Left => {
+                thermographs
+                    .iter()
+                    .map(|thermograph| thermograph.right_wall.value_at(temperature))
+                    .fold(Rational::from(0), |acc, wall| acc + wall)
+                    + max_width
+            }
+            Player::Right => {
+                -(thermographs
+                    .iter()
+                    .map(|thermograph| thermograph.left_wall.value_at(temperature))
+                    .fold(Rational::from(0), |acc, wall| acc + wall)
+                    - max_width)
+            }
+        }
+    };
+
+    // Prefer higher temperatures on ties, so hot components are not neglected
+    let temperature = temperatures
+        .into_iter()
+        .map(|temperature| (compound_at(temperature), temperature))
+        .max()?
+        .1;
+    let component = playable
+        .into_iter()
+        .map(|component| (width_at(&thermographs[component], temperature), component))
+        .reduce(|best, current| if current.0 > best.0 { current } else { best })?
+        .1;
+    Some((component, temperature))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::{
+        games::domineering::Domineering, transposition_table::ParallelTranspositionTable,
+    };
+    use std::str::FromStr;
+
+    fn components(positions: &[&str]) -> Vec<Domineering> {
+        positions
+            .iter()
+            .map(|position| Domineering::from_str(position).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn winning_moves_win() {
+        let tt = ParallelTranspositionTable::new();
+        let strategy = Strategy::new(&tt);
+        for sum in [
+            &["..|..", "...", "."][..],
+            &["...|...", ".|."],
+            &["..#|...", "....", ".|.|."],
+            &["...|..#", "..|.#"],
+        ] {
+            let components = components(sum);
+            let total = components
+                .iter()
+                .map(|component| component.canonical_form(&tt))
+                .sum::<CanonicalForm>();
+            for player in [Player::Left, Player::Right] {
+                let winning = strategy.winning_moves(&components, player);
+                assert_eq!(
+                    !winning.is_empty(),
+                    total.outcome().wins_moving_first(player),
+                    "{sum:?}"
+                );
+                for SumMove {
+                    component,
+                    position,
+                } in winning
+                {
+                    let mut after = components.clone();
+                    after[component] = position;
+                    let after = after
+                        .iter()
+                        .map(|component| component.canonical_form(&tt))
+                        .sum::<CanonicalForm>();
+                    assert!(!after.outcome().wins_moving_first(player.opposite()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn heuristics_choose_hot_components() {
+        let tt = ParallelTranspositionTable::new();
+        let strategy = Strategy::new(&tt);
+
+        // Components with increasing temperatures
+        let sum = components(&["..|#.", "..|..", "...|...|..#"]);
+        let temperatures = sum
+            .iter()
+            .map(|component| component.canonical_form(&tt).temperature().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(temperatures, ["0", "1", "3/2"]);
+
+        for heuristic in [
+            Heuristic::Hotstrat,
+            Heuristic::Thermostrat,
+            Heuristic::Sentestrat {
+                last_component: None,
+            },
+        ] {
+            for player in [Player::Left, Player::Right] {
+                let chosen = strategy.heuristic_move(&sum, player, heuristic).unwrap();
+                assert_eq!(chosen.component, 2, "{heuristic:?}");
+            }
+        }
+
+        let chosen = strategy
+            .heuristic_move(
+                &sum,
+                Player::Left,
+                Heuristic::Sentestrat {
+                    last_component: Some(1),
+                },
+            )
+            .unwrap();
+        assert_eq!(chosen.component, 2);
+
+        assert_eq!(
+            strategy.best_move(&components(&["#"]), Player::Left, Heuristic::Hotstrat),
+            None
+        );
+    }
+}
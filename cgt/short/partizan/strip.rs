@@ -0,0 +1,332 @@
+//! Strip games - partizan games played on one or more independent one-dimensional rows of tiles
+
+use crate::{
+    drawing::{self, BoundingBox, Canvas, Color, Draw},
+    encoding::Encode,
+    grid::{CharTile, FiniteGrid, Grid, vec_grid::VecGrid},
+    numeric::v2f::V2f,
+};
+use std::{
+    fmt::{self, Display, Write},
+    str::FromStr,
+};
+
+/// Tile of a strip game
+pub trait StripTile: CharTile + Copy + Eq {
+    /// Get the way the tile is drawn
+    fn drawing_tile(self) -> drawing::Tile;
+}
+
+/// Single row of tiles
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Strip<T> {
+    tiles: Vec<T>,
+}
+
+impl<T> Strip<T> {
+    /// Create new strip from a row of tiles
+    #[inline]
+    pub const fn new(tiles: Vec<T>) -> Self {
+        Self { tiles }
+    }
+
+    /// Get tiles of the strip
+    #[inline]
+    pub const fn tiles(&self) -> &Vec<T> {
+        &self.tiles
+    }
+
+    /// Get tiles of the strip mutably
+    #[inline]
+    pub const fn tiles_mut(&mut self) -> &mut Vec<T> {
+        &mut self.tiles
+    }
+
+    /// Get number of tiles in the strip
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Check if the strip has no tiles
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+}
+
+impl<T> Strip<T>
+where
+    T: StripTile,
+{
+    /// Construct grid of height 1 equal to the strip
+    ///
+    /// # Errors
+    /// - Strip has more than 255 tiles
+    pub fn grid(&self) -> Option<VecGrid<T>> {
+        let Some(&first) = self.tiles.first() else {
+            return Some(VecGrid::zero_size());
+        };
+        let width = u8::try_from(self.tiles.len()).ok()?;
+        let mut grid = VecGrid::filled(width, 1, first)?;
+        for (x, tile) in (0..width).zip(self.tiles.iter().copied()) {
+            grid.set(x, 0, tile);
+        }
+        Some(grid)
+    }
+
+    /// Output strip as LaTeX `TikZ` picture
+    pub fn to_latex(&self) -> String {
+        rows_to_latex(std::slice::from_ref(self))
+    }
+}
+
+impl<T> Display for Strip<T>
+where
+    T: CharTile + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for tile in &self.tiles {
+            write!(f, "{}", tile.tile_to_char())?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> FromStr for Strip<T>
+where
+    T: CharTile,
+{
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.chars()
+            .map(|c| T::char_to_tile(c).ok_or(()))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self::new)
+    }
+}
+
+impl<T> Encode for Strip<T>
+where
+    T: CharTile + Copy,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.tiles.len().encode(buf);
+        for tile in &self.tiles {
+            tile.tile_to_char().encode(buf);
+        }
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        let len = usize::decode(input)?;
+        (0..len)
+            .map(|_| T::char_to_tile(char::decode(input)?))
+            .collect::<Option<Vec<_>>>()
+            .map(Self::new)
+    }
+}
+
+impl<T> Draw for Strip<T>
+where
+    T: StripTile,
+{
+    fn draw<C>(&self, canvas: &mut C)
+    where
+        C: Canvas,
+    {
+        draw_rows(std::slice::from_ref(self), canvas);
+    }
+
+    fn required_canvas<C>(&self) -> BoundingBox
+    where
+        C: Canvas,
+    {
+        rows_canvas::<T, C>(std::slice::from_ref(self))
+    }
+}
+
+/// Display rows using `|` as row separator
+#[allow(clippy::missing_errors_doc)]
+pub fn display_rows<T>(w: &mut impl Write, rows: &[Strip<T>]) -> fmt::Result
+where
+    T: CharTile + Copy,
+{
+    for (idx, row) in rows.iter().enumerate() {
+        if idx != 0 {
+            write!(w, "|")?;
+        }
+        write!(w, "{row}")?;
+    }
+    Ok(())
+}
+
+/// Parse rows following notation from [`display_rows`]
+pub fn parse_rows<T>(input: &str) -> Option<Vec<Strip<T>>>
+where
+    T: CharTile,
+{
+    input
+        .split('|')
+        .map(|row| Strip::from_str(row).ok())
+        .collect()
+}
+
+/// Paint rows on existing canvas, one below another
+pub fn draw_rows<T, C>(rows: &[Strip<T>], canvas: &mut C)
+where
+    T: StripTile,
+    C: Canvas,
+{
+    for (y, row) in rows.iter().enumerate() {
+        for (x, tile) in row.tiles.iter().enumerate() {
+            canvas.tile(
+                C::wide_tile_position(x as u64, y as u64),
+                tile.drawing_tile(),
+            );
+        }
+        canvas.grid(C::wide_tile_position(0, y as u64), row.len() as u32, 1);
+    }
+}
+
+/// Minimum required canvas size to paint rows with [`draw_rows`]
+pub fn rows_canvas<T, C>(rows: &[Strip<T>]) -> BoundingBox
+where
+    C: Canvas,
+{
+    let width = rows.iter().map(Strip::len).max().unwrap_or(0) as f32;
+    let height = rows.len() as f32;
+    let tile_size = C::tile_size();
+    let grid_weight = C::thick_line_weight();
+    BoundingBox {
+        top_left: V2f::ZERO,
+        bottom_right: V2f {
+            x: tile_size.x.mul_add(width, grid_weight * (width + 1.0)),
+            y: tile_size.y.mul_add(height, grid_weight * (height + 1.0)),
+        },
+    }
+}
+
+/// Output rows as LaTeX `TikZ` picture, one below another. Light gray is the background color so
+/// such tiles are not filled.
+pub fn rows_to_latex<T>(rows: &[Strip<T>]) -> String
+where
+    T: StripTile,
+{
+    fn color(color: Color) -> String {
+        format!(
+            "{{rgb,255:red,{};green,{};blue,{}}}",
+            color.r, color.g, color.b
+        )
+    }
+
+    fn fill(buf: &mut String, tile_color: Color, x: usize, y: usize) {
+        if tile_color != Color::LIGHT_GRAY {
+            write!(
+                buf,
+                "\\fill[fill={}] ({},{}) rectangle ({},{}); ",
+                color(tile_color),
+                x,
+                y,
+                x + 1,
+                y + 1
+            )
+            .unwrap();
+        }
+    }
+
+    let mut buf = String::new();
+    write!(buf, "\\begin{{tikzpicture}}[scale=1] ").unwrap();
+    for (row_idx, row) in rows.iter().enumerate() {
+        let y = rows.len() - row_idx - 1;
+        for (x, tile) in row.tiles.iter().enumerate() {
+            match tile.drawing_tile() {
+                drawing::Tile::Square { color: tile_color } => fill(&mut buf, tile_color, x, y),
+                drawing::Tile::Circle {
+                    tile_color,
+                    circle_color,
+                } => {
+                    fill(&mut buf, tile_color, x, y);
+                    write!(
+                        buf,
+                        "\\fill[fill={}] ({}.5,{}.5) circle (0.4); ",
+                        color(circle_color),
+                        x,
+                        y
+                    )
+                    .unwrap();
+                }
+                drawing::Tile::Char {
+                    tile_color,
+                    text_color,
+                    letter,
+                } => {
+                    fill(&mut buf, tile_color, x, y);
+                    write!(
+                        buf,
+                        "\\node[text={}] at ({}.5,{}.5) {{{}}}; ",
+                        color(text_color),
+                        x,
+                        y,
+                        letter
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        write!(
+            buf,
+            "\\draw[step=1cm,black] (0,{}) grid ({},{}); ",
+            y,
+            row.len(),
+            y + 1
+        )
+        .unwrap();
+    }
+    write!(buf, "\\end{{tikzpicture}}").unwrap();
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{drawing::svg, short::partizan::games::toppling_dominoes::Tile};
+
+    #[test]
+    fn encoding_roundtrip() {
+        let rows = parse_rows::<Tile>("xog||gg").unwrap();
+        let mut buf = Vec::new();
+        rows.encode(&mut buf);
+        assert_eq!(Vec::<Strip<Tile>>::decode(&mut buf.as_slice()), Some(rows));
+    }
+
+    #[test]
+    fn long_strips() {
+        let strip = Strip::new(vec![Tile::Blue; 300]);
+        assert!(strip.grid().is_none());
+        assert_eq!(
+            Strip::new(vec![Tile::Blue; 255])
+                .grid()
+                .map(|grid| grid.width()),
+            Some(255)
+        );
+
+        let mut canvas = svg::Canvas::new(strip.required_canvas::<svg::Canvas>());
+        strip.draw(&mut canvas);
+    }
+
+    #[test]
+    fn latex_rows() {
+        let rows = parse_rows::<Tile>("x|o").unwrap();
+        assert_eq!(
+            rows_to_latex(&rows),
+            "\\begin{tikzpicture}[scale=1] \
+             \\fill[fill={rgb,255:red,78;green,74;blue,251}] (0,1) rectangle (1,2); \
+             \\draw[step=1cm,black] (0,1) grid (1,2); \
+             \\fill[fill={rgb,255:red,249;green,38;blue,114}] (0,0) rectangle (1,1); \
+             \\draw[step=1cm,black] (0,0) grid (1,1); \
+             \\end{tikzpicture}"
+        );
+    }
+}
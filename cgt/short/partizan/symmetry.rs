@@ -0,0 +1,123 @@
+//! Symmetry-aware transposition table for games played on grids
+
+use crate::{
+    grid::Symmetry,
+    short::partizan::{canonical_form::CanonicalForm, transposition_table::TranspositionTable},
+};
+
+/// Game position that can be transformed by symmetries of its board
+pub trait SymmetricPosition: Sized {
+    /// Apply symmetry to the position
+    #[must_use]
+    fn transform(&self, symmetry: Symmetry) -> Self;
+
+    /// Check if the symmetry swaps roles of the players, i.e. value of the transformed position
+    /// is the negative of the original value
+    fn swaps_players(symmetry: Symmetry) -> bool;
+
+    /// Get the smallest position among all symmetric positions, and whether its value is the
+    /// negative of the original value
+    fn symmetry_representative(&self) -> (Self, bool)
+    where
+        Self: Ord,
+    {
+        Symmetry::ALL
+            .into_iter()
+            .map(|symmetry| (self.transform(symmetry), Self::swaps_players(symmetry)))
+            .min()
+            .expect("there is at least one symmetry")
+    }
+}
+
+/// Transposition table that stores only one position from each set of symmetric positions
+#[derive(Debug, Clone, Default)]
+pub struct SymmetricTranspositionTable<TT> {
+    inner: TT,
+}
+
+impl<TT> SymmetricTranspositionTable<TT> {
+    /// Wrap existing transposition table
+    #[inline]
+    pub const fn new(inner: TT) -> Self {
+        Self { inner }
+    }
+
+    /// Get the underlying transposition table
+    #[inline]
+    pub const fn inner(&self) -> &TT {
+        &self.inner
+    }
+}
+
+impl<G, TT> TranspositionTable<G> for SymmetricTranspositionTable<TT>
+where
+    G: SymmetricPosition + Ord,
+    TT: TranspositionTable<G>,
+{
+    fn lookup_position(&self, position: &G) -> Option<CanonicalForm> {
+        let (representative, negate) = position.symmetry_representative();
+        let value = self.inner.lookup_position(&representative)?;
+        Some(if negate { -value } else { value })
+    }
+
+    fn insert_position(&self, position: G, value: CanonicalForm) {
+        let (representative, negate) = position.symmetry_representative();
+        let value = if negate { -value } else { value };
+        self.inner.insert_position(representative, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::{
+        games::{amazons::Amazons, domineering::Domineering, fission::Fission, konane::Konane},
+        partizan_game::PartizanGame,
+        transposition_table::{NoTranspositionTable, ParallelTranspositionTable},
+    };
+    use std::{fmt::Debug, str::FromStr};
+
+    fn assert_symmetric<G>(positions: &[&str])
+    where
+        G: SymmetricPosition + PartizanGame + FromStr + Ord + Sync + Debug,
+        G::Err: Debug,
+    {
+        let table = SymmetricTranspositionTable::new(ParallelTranspositionTable::new());
+        for position in positions {
+            let position = G::from_str(position).unwrap();
+            let expected = position.canonical_form(&NoTranspositionTable::new());
+            for symmetry in Symmetry::ALL {
+                let transformed = position.transform(symmetry);
+                let expected = if G::swaps_players(symmetry) {
+                    -&expected
+                } else {
+                    expected.clone()
+                };
+                assert_eq!(
+                    transformed.canonical_form(&table),
+                    expected,
+                    "{position:?} {symmetry:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn symmetric_values() {
+        assert_symmetric::<Domineering>(&["..#|...|...", ".#..|....", "...|.#.", "..|..|.."]);
+        assert_symmetric::<Fission>(&["....|.x..|....", "...|.x.|..."]);
+        assert_symmetric::<Konane>(&["xo..|..ox", "x.|o.|.x"]);
+        assert_symmetric::<Amazons>(&["x..|..o", "x.#|..o"]);
+
+        let table =
+            SymmetricTranspositionTable::new(ParallelTranspositionTable::<Domineering>::new());
+        Domineering::from_str("..|..|..")
+            .unwrap()
+            .canonical_form(&table);
+        let stored = table.inner().len();
+        Domineering::from_str("...|...")
+            .unwrap()
+            .canonical_form(&table);
+        assert_eq!(table.inner().len(), stored);
+    }
+}
@@ -1,29 +1,46 @@
 //! Thread safe transposition table for game values
 
-use crate::{short::partizan::canonical_form::CanonicalForm, total::TotalWrapper};
+use crate::{
+    encoding::Encode,
+    short::partizan::canonical_form::CanonicalForm,
+    total::{TotalWrappable, TotalWrapper},
+};
 use append_only_vec::AppendOnlyVec;
 use dashmap::DashMap;
-use std::{fmt::Debug, hash::Hash, marker::PhantomData};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    fs::{File, OpenOptions},
+    hash::Hash,
+    io::{self, Read, Write},
+    marker::PhantomData,
+    path::Path,
+    sync::{
+        Mutex,
+        atomic::{self, AtomicU64},
+    },
+};
 
-/// Interface of a transposition table
-pub trait TranspositionTable<G> {
+/// Interface of a transposition table storing values of type `V`, canonical forms by default
+pub trait TranspositionTable<G, V = CanonicalForm> {
     /// Lookup a position value if exists
-    fn lookup_position(&self, position: &G) -> Option<CanonicalForm>;
+    fn lookup_position(&self, position: &G) -> Option<V>;
 
     /// Save position and its game value
-    fn insert_position(&self, position: G, value: CanonicalForm);
+    fn insert_position(&self, position: G, value: V);
 }
 
-/// Transaction table (cache) of game positions and canonical forms.
-pub struct ParallelTranspositionTable<G> {
-    values: AppendOnlyVec<CanonicalForm>,
+/// Transaction table (cache) of game positions and their values. Equal values are stored once.
+pub struct ParallelTranspositionTable<G, V = CanonicalForm> {
+    values: AppendOnlyVec<V>,
     positions: DashMap<G, usize, ahash::RandomState>,
-    known_values: DashMap<TotalWrapper<CanonicalForm>, usize, ahash::RandomState>,
+    known_values: DashMap<TotalWrapper<V>, usize, ahash::RandomState>,
 }
 
-impl<G> ParallelTranspositionTable<G>
+impl<G, V> ParallelTranspositionTable<G, V>
 where
     G: Eq + Hash,
+    V: TotalWrappable + Clone,
 {
     /// Create new empty transposition table.
     #[inline]
@@ -42,11 +59,26 @@ where
     pub fn is_empty(&self) -> bool {
         self.positions.is_empty()
     }
+
+    /// Save position and return id of its value, and whether the value was not known before
+    fn insert_position_with_id(&self, position: G, value: V) -> (usize, bool) {
+        if let Some(known) = self.known_values.get(TotalWrapper::from_ref(&value)) {
+            let known = *known;
+            self.positions.insert(position, known);
+            (known, false)
+        } else {
+            let inserted = self.values.push(value.clone());
+            self.known_values.insert(TotalWrapper::new(value), inserted);
+            self.positions.insert(position, inserted);
+            (inserted, true)
+        }
+    }
 }
 
-impl<G> Debug for ParallelTranspositionTable<G>
+impl<G, V> Debug for ParallelTranspositionTable<G, V>
 where
     G: Debug + Hash + Eq,
+    V: Debug + TotalWrappable,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let ParallelTranspositionTable {
@@ -63,9 +95,10 @@ where
     }
 }
 
-impl<G> Default for ParallelTranspositionTable<G>
+impl<G, V> Default for ParallelTranspositionTable<G, V>
 where
     G: Hash + Eq,
+    V: TotalWrappable,
 {
     #[inline]
     fn default() -> Self {
@@ -77,13 +110,14 @@ where
     }
 }
 
-impl<G> TranspositionTable<G> for ParallelTranspositionTable<G>
+impl<G, V> TranspositionTable<G, V> for ParallelTranspositionTable<G, V>
 where
     G: Eq + Hash,
+    V: TotalWrappable + Clone,
 {
     #[allow(clippy::missing_panics_doc)]
     #[inline]
-    fn lookup_position(&self, position: &G) -> Option<CanonicalForm> {
+    fn lookup_position(&self, position: &G) -> Option<V> {
         self.positions
             .get(position)
             .map(|id| self.values[*id].clone())
@@ -91,13 +125,468 @@ where
 
     #[allow(clippy::missing_panics_doc)]
     #[inline]
+    fn insert_position(&self, position: G, value: V) {
+        self.insert_position_with_id(position, value);
+    }
+}
+
+/// Magic bytes at the beginning of a persistent transposition table file, followed by version
+const PERSISTENT_MAGIC: &[u8; 5] = b"CGTTT";
+
+/// Version of the persistent transposition table file format
+const PERSISTENT_VERSION: u8 = 1;
+
+/// Record tag of a new distinct game value. Values get consecutive ids in order of appearance.
+const RECORD_VALUE: u8 = 0;
+
+/// Record tag of a position followed by id of its value
+const RECORD_POSITION: u8 = 1;
+
+/// Size of buffered records that triggers writing them to the file
+const FLUSH_THRESHOLD: usize = 1 << 20;
+
+/// Transposition table backed by an append-only file, so long searches can be resumed.
+///
+/// File consists of a header and a sequence of records, each either a new distinct game value
+/// or a position referring to a previously stored value. Records are buffered in memory and
+/// appended to the file in batches. Truncated record at the end of the file, e.g. after a crash
+/// during write, is discarded on load. After a failed write new positions are kept only in memory.
+pub struct PersistentTranspositionTable<G> {
+    table: ParallelTranspositionTable<G>,
+    log: Mutex<PersistentLog>,
+}
+
+struct PersistentLog {
+    file: File,
+    pending: Vec<u8>,
+
+    /// First failed write. File may end with a partially written record after it, so nothing
+    /// more is written.
+    error: Option<io::Error>,
+}
+
+impl PersistentLog {
+    fn write_pending(&mut self) -> io::Result<()> {
+        if let Some(error) = &self.error {
+            return Err(io::Error::new(error.kind(), error.to_string()));
+        }
+        if let Err(error) = self.file.write_all(&self.pending) {
+            // Release the buffer, records are not written anymore
+            self.pending = Vec::new();
+            let reported = io::Error::new(error.kind(), error.to_string());
+            self.error = Some(error);
+            return Err(reported);
+        }
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+impl<G> PersistentTranspositionTable<G>
+where
+    G: Encode + Eq + Hash,
+{
+    /// Open table stored in a file, creating the file if it does not exist. All positions stored
+    /// in the file are loaded, and new positions will be appended to it.
+    ///
+    /// # Errors
+    /// - File cannot be read or written
+    /// - File is not a transposition table
+    pub fn open<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)?;
+
+        let table = ParallelTranspositionTable::new();
+        let valid_len = if contents.is_empty() {
+            file.write_all(PERSISTENT_MAGIC)?;
+            file.write_all(&[PERSISTENT_VERSION])?;
+            PERSISTENT_MAGIC.len() + 1
+        } else {
+            Self::load_records(&table, &contents)?
+        };
+
+        // Drop partially written record so new records are appended after a valid one
+        if valid_len < contents.len() {
+            file.set_len(valid_len as u64)?;
+        }
+
+        Ok(Self {
+            table,
+            log: Mutex::new(PersistentLog {
+                file,
+                pending: Vec::new(),
+                error: None,
+            }),
+        })
+    }
+
+    /// Load records into the table and return length of the valid prefix of the contents
+    fn load_records(table: &ParallelTranspositionTable<G>, contents: &[u8]) -> io::Result<usize> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "not a transposition table");
+
+        let Some(mut input) = contents.strip_prefix(PERSISTENT_MAGIC.as_slice()) else {
+            return Err(invalid());
+        };
+        if u8::decode(&mut input) != Some(PERSISTENT_VERSION) {
+            return Err(invalid());
+        }
+
+        loop {
+            let valid_len = contents.len() - input.len();
+            let mut record = input;
+            match u8::decode(&mut record) {
+                Some(RECORD_VALUE) => {
+                    let Some(value) = CanonicalForm::decode(&mut record) else {
+                        return Ok(valid_len);
+                    };
+                    let inserted = table.values.push(value.clone());
+                    table
+                        .known_values
+                        .insert(TotalWrapper::new(value), inserted);
+                }
+                Some(RECORD_POSITION) => {
+                    let Some(position) = G::decode(&mut record) else {
+                        return Ok(valid_len);
+                    };
+                    let Some(id) = usize::decode(&mut record) else {
+                        return Ok(valid_len);
+                    };
+                    if id >= table.values.len() {
+                        return Err(invalid());
+                    }
+                    table.positions.insert(position, id);
+                }
+                Some(_) => return Err(invalid()),
+                None => return Ok(valid_len),
+            }
+            input = record;
+        }
+    }
+
+    /// Write all buffered positions to the file and wait until they reach the disk
+    ///
+    /// # Errors
+    /// - Writing to the file failed, now or during any earlier write
+    pub fn flush(&self) -> io::Result<()> {
+        let mut log = self.log.lock().unwrap();
+        log.write_pending()?;
+        log.file.sync_data()
+    }
+
+    /// Get number of saved positions
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Check if table stores any position
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+}
+
+impl<G> Debug for PersistentTranspositionTable<G>
+where
+    G: Debug + Hash + Eq,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PersistentTranspositionTable")
+            .field("table", &self.table)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G> TranspositionTable<G> for PersistentTranspositionTable<G>
+where
+    G: Encode + Eq + Hash,
+{
+    #[inline]
+    fn lookup_position(&self, position: &G) -> Option<CanonicalForm> {
+        self.table.lookup_position(position)
+    }
+
     fn insert_position(&self, position: G, value: CanonicalForm) {
-        if let Some(known) = self.known_values.get(TotalWrapper::from_ref(&value)) {
-            self.positions.insert(position, *known);
+        let mut encoded_position = Vec::new();
+        position.encode(&mut encoded_position);
+
+        // Lock is held during insertion, so value records are written before any position
+        // that refers to them, and value ids match the order of records
+        let mut log = self.log.lock().unwrap();
+        let (id, is_new) = self.table.insert_position_with_id(position, value);
+        if log.error.is_some() {
+            return;
+        }
+        if is_new {
+            RECORD_VALUE.encode(&mut log.pending);
+            self.table.values[id].encode(&mut log.pending);
+        }
+        RECORD_POSITION.encode(&mut log.pending);
+        log.pending.extend_from_slice(&encoded_position);
+        id.encode(&mut log.pending);
+
+        if log.pending.len() >= FLUSH_THRESHOLD {
+            // Error is kept in the log and reported by the next flush
+            let _ = log.write_pending();
+        }
+    }
+}
+
+impl<G> Drop for PersistentTranspositionTable<G> {
+    fn drop(&mut self) {
+        if let Ok(log) = self.log.get_mut() {
+            let _ = log.write_pending();
+        }
+    }
+}
+
+/// Maximum number of independently locked parts of [`BoundedTranspositionTable`]
+const BOUNDED_SHARDS: usize = 64;
+
+/// Minimum capacity of a single part of [`BoundedTranspositionTable`], so positions that hash
+/// unevenly do not leave most of the capacity of a small table unused
+const MIN_SHARD_CAPACITY: usize = 1024;
+
+/// Statistics of transposition table usage
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TranspositionTableStats {
+    /// Number of lookups that found a position
+    pub hits: u64,
+
+    /// Number of lookups that did not find a position
+    pub misses: u64,
+
+    /// Number of saved positions
+    pub insertions: u64,
+
+    /// Number of positions removed to make space for new ones
+    pub evictions: u64,
+}
+
+impl TranspositionTableStats {
+    /// Fraction of lookups that found a position
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
         } else {
-            let inserted = self.values.push(value.clone());
-            self.known_values.insert(TotalWrapper::new(value), inserted);
-            self.positions.insert(position, inserted);
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+impl Display for TranspositionTableStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "hits: {}, misses: {}, insertions: {}, evictions: {}",
+            self.hits, self.misses, self.insertions, self.evictions
+        )
+    }
+}
+
+/// Transposition table holding at most a given number of positions. When full, positions are
+/// evicted using the clock (second chance) policy, approximating least recently used eviction.
+pub struct BoundedTranspositionTable<G, V = CanonicalForm> {
+    shards: Vec<Mutex<ClockShard<G, V>>>,
+    hasher: ahash::RandomState,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    insertions: AtomicU64,
+    evictions: AtomicU64,
+}
+
+struct ClockEntry<G, V> {
+    position: G,
+    value: V,
+    referenced: bool,
+}
+
+struct ClockShard<G, V> {
+    capacity: usize,
+    entries: Vec<ClockEntry<G, V>>,
+    index: HashMap<G, usize, ahash::RandomState>,
+    hand: usize,
+}
+
+impl<G, V> ClockShard<G, V>
+where
+    G: Clone + Eq + Hash,
+    V: Clone,
+{
+    fn lookup(&mut self, position: &G) -> Option<V> {
+        let entry = &mut self.entries[*self.index.get(position)?];
+        entry.referenced = true;
+        Some(entry.value.clone())
+    }
+
+    /// Insert position and return `true` if other position was evicted
+    fn insert(&mut self, position: G, value: V) -> bool {
+        if let Some(&slot) = self.index.get(&position) {
+            self.entries[slot].value = value;
+            return false;
+        }
+
+        if self.entries.len() < self.capacity {
+            self.index.insert(position.clone(), self.entries.len());
+            self.entries.push(ClockEntry {
+                position,
+                value,
+                referenced: false,
+            });
+            return false;
+        }
+
+        // Clear reference bits until an entry that was not used since last pass is found
+        while self.entries[self.hand].referenced {
+            self.entries[self.hand].referenced = false;
+            self.hand = (self.hand + 1) % self.entries.len();
+        }
+        let slot = self.hand;
+        self.hand = (self.hand + 1) % self.entries.len();
+
+        self.index.remove(&self.entries[slot].position);
+        self.index.insert(position.clone(), slot);
+        self.entries[slot] = ClockEntry {
+            position,
+            value,
+            referenced: false,
+        };
+        true
+    }
+}
+
+impl<G, V> BoundedTranspositionTable<G, V>
+where
+    G: Clone + Eq + Hash,
+    V: Clone,
+{
+    /// Create new empty transposition table that stores at most `capacity` positions
+    ///
+    /// Capacity is split evenly between up to 64 independently locked shards, each holding at
+    /// least 1024 positions. Position is always stored in the same shard, so a shard may evict
+    /// positions before the whole table is full.
+    ///
+    /// # Panics
+    /// - `capacity` is zero
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be positive");
+        let shards = (capacity / MIN_SHARD_CAPACITY).clamp(1, BOUNDED_SHARDS);
+        Self {
+            shards: (0..shards)
+                .map(|shard| {
+                    // Spread capacity so shard capacities sum up to the total
+                    let capacity = capacity / shards + usize::from(shard < capacity % shards);
+                    Mutex::new(ClockShard {
+                        capacity,
+                        entries: Vec::new(),
+                        index: HashMap::default(),
+                        hand: 0,
+                    })
+                })
+                .collect(),
+            // Fixed seeds so positions are assigned to the same shards on every run
+            hasher: ahash::RandomState::with_seeds(0, 0, 0, 0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            insertions: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    /// Create new empty transposition table that uses approximately `bytes` bytes of memory.
+    /// Memory owned by stored values, like options of canonical forms, is not taken into account.
+    pub fn with_memory_budget(bytes: usize) -> Self {
+        let entry_size = size_of::<ClockEntry<G, V>>() + 2 * size_of::<(G, usize)>();
+        Self::new((bytes / entry_size).max(1))
+    }
+
+    /// Maximum number of stored positions
+    pub fn capacity(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.lock().unwrap().capacity)
+            .sum()
+    }
+
+    /// Get number of saved positions
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.lock().unwrap().entries.len())
+            .sum()
+    }
+
+    /// Check if table stores any position
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get usage statistics
+    pub fn stats(&self) -> TranspositionTableStats {
+        TranspositionTableStats {
+            hits: self.hits.load(atomic::Ordering::Relaxed),
+            misses: self.misses.load(atomic::Ordering::Relaxed),
+            insertions: self.insertions.load(atomic::Ordering::Relaxed),
+            evictions: self.evictions.load(atomic::Ordering::Relaxed),
+        }
+    }
+
+    fn shard(&self, position: &G) -> &Mutex<ClockShard<G, V>> {
+        let hash = self.hasher.hash_one(position);
+        &self.shards[(hash % self.shards.len() as u64) as usize]
+    }
+}
+
+impl<G, V> Debug for BoundedTranspositionTable<G, V>
+where
+    G: Clone + Eq + Hash,
+    V: Clone,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoundedTranspositionTable")
+            .field("capacity", &self.capacity())
+            .field("len", &self.len())
+            .field("stats", &self.stats())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G, V> TranspositionTable<G, V> for BoundedTranspositionTable<G, V>
+where
+    G: Clone + Eq + Hash,
+    V: Clone,
+{
+    fn lookup_position(&self, position: &G) -> Option<V> {
+        let found = self.shard(position).lock().unwrap().lookup(position);
+        let counter = if found.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, atomic::Ordering::Relaxed);
+        found
+    }
+
+    fn insert_position(&self, position: G, value: V) {
+        let evicted = self
+            .shard(&position)
+            .lock()
+            .unwrap()
+            .insert(position, value);
+        self.insertions.fetch_add(1, atomic::Ordering::Relaxed);
+        if evicted {
+            self.evictions.fetch_add(1, atomic::Ordering::Relaxed);
         }
     }
 }
@@ -121,12 +610,153 @@ impl<G> Default for NoTranspositionTable<G> {
     }
 }
 
-impl<G> TranspositionTable<G> for NoTranspositionTable<G> {
+impl<G, V> TranspositionTable<G, V> for NoTranspositionTable<G> {
     #[inline]
-    fn lookup_position(&self, _position: &G) -> Option<CanonicalForm> {
+    fn lookup_position(&self, _position: &G) -> Option<V> {
         None
     }
 
     #[inline]
-    fn insert_position(&self, _position: G, _value: CanonicalForm) {}
+    fn insert_position(&self, _position: G, _value: V) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short::partizan::{games::domineering::Domineering, partizan_game::PartizanGame};
+    use std::str::FromStr;
+
+    #[test]
+    fn bounded_table_evicts() {
+        let table = BoundedTranspositionTable::new(3);
+        assert_eq!(table.shards.len(), 1);
+        for i in 0..10 {
+            table.insert_position(i, CanonicalForm::new_integer(i));
+        }
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.capacity(), 3);
+
+        let found = (0..10)
+            .filter(|i| table.lookup_position(i) == Some(CanonicalForm::new_integer(*i)))
+            .count();
+        assert_eq!(found, 3);
+        assert_eq!(
+            table.stats(),
+            TranspositionTableStats {
+                hits: 3,
+                misses: 7,
+                insertions: 10,
+                evictions: 7,
+            }
+        );
+
+        // Canonical form does not depend on the table size
+        let position: Domineering = Domineering::from_str("....|....|....").unwrap();
+        let expected = position.canonical_form(&NoTranspositionTable::new());
+        for capacity in [1, 5, 100, 10000] {
+            let table = BoundedTranspositionTable::new(capacity);
+            assert_eq!(position.canonical_form(&table), expected);
+            assert_eq!(position.canonical_form(&table), expected);
+            assert!(table.len() <= capacity);
+        }
+    }
+
+    #[test]
+    fn bounded_table_splits_capacity() {
+        let table = BoundedTranspositionTable::<u32>::new(5000);
+        assert_eq!(table.shards.len(), 4);
+        assert_eq!(table.capacity(), 5000);
+
+        let table = BoundedTranspositionTable::<u32>::new(1_000_003);
+        assert_eq!(table.shards.len(), BOUNDED_SHARDS);
+        assert_eq!(table.capacity(), 1_000_003);
+    }
+
+    #[test]
+    fn clock_gives_second_chance() {
+        let mut shard = ClockShard {
+            capacity: 2,
+            entries: Vec::new(),
+            index: HashMap::default(),
+            hand: 0,
+        };
+        shard.insert(0, CanonicalForm::new_integer(0));
+        shard.insert(1, CanonicalForm::new_integer(1));
+        assert!(shard.lookup(&0).is_some());
+        assert!(shard.insert(2, CanonicalForm::new_integer(2)));
+        assert!(shard.lookup(&0).is_some());
+        assert!(shard.lookup(&1).is_none());
+    }
+
+    #[test]
+    fn persistent_table_resumes() {
+        let path = std::env::temp_dir().join(format!(
+            "cgt-persistent-transposition-table-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let positions = ["..|..|..", "...|...", "#..|...|..#", "....|.##."]
+            .map(|position| Domineering::from_str(position).unwrap());
+        let expected = positions
+            .iter()
+            .map(|position| position.canonical_form(&NoTranspositionTable::new()))
+            .collect::<Vec<_>>();
+
+        let stored = {
+            let table = PersistentTranspositionTable::open(&path).unwrap();
+            for position in &positions {
+                position.canonical_form(&table);
+            }
+            table.flush().unwrap();
+            table.len()
+        };
+        assert!(stored >= positions.len());
+
+        let table = PersistentTranspositionTable::<Domineering>::open(&path).unwrap();
+        assert_eq!(table.len(), stored);
+        for (position, expected) in positions.iter().zip(&expected) {
+            assert_eq!(table.lookup_position(position).as_ref(), Some(expected));
+        }
+        drop(table);
+
+        // Partially written record is dropped
+        let len = std::fs::metadata(&path).unwrap().len();
+        let file = OpenOptions::new().write(true).open(&path).unwrap();
+        file.set_len(len - 1).unwrap();
+        drop(file);
+        let table = PersistentTranspositionTable::<Domineering>::open(&path).unwrap();
+        assert_eq!(table.len(), stored - 1);
+        drop(table);
+
+        std::fs::write(&path, b"garbage").unwrap();
+        assert!(PersistentTranspositionTable::<Domineering>::open(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn persistent_table_stops_logging_after_error() {
+        let path = std::env::temp_dir().join(format!(
+            "cgt-persistent-transposition-table-error-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let table = PersistentTranspositionTable::<u64>::open(&path).unwrap();
+        // Writing to a read-only handle fails
+        table.log.lock().unwrap().file = File::open(&path).unwrap();
+        table.insert_position(0, CanonicalForm::new_integer(0));
+        assert!(table.flush().is_err());
+
+        table.insert_position(1, CanonicalForm::new_integer(1));
+        assert!(table.log.lock().unwrap().pending.is_empty());
+        assert_eq!(
+            table.lookup_position(&1),
+            Some(CanonicalForm::new_integer(1))
+        );
+        assert!(table.flush().is_err());
+
+        drop(table);
+        std::fs::remove_file(&path).unwrap();
+    }
 }
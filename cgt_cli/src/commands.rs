@@ -2,9 +2,13 @@ crate::clap_utils::mk_subcommand! {
     Domineering => domineering,
     Snort => snort,
     Quicksort => quicksort,
+    PartizanQuicksort => partizan_quicksort,
+    PartizanSubtraction => partizan_subtraction,
     WindUp => wind_up,
     CanonicalForm => canonical_form,
     Amazons => amazons,
+    Clobber => clobber,
+    Cherries => cherries,
     LeftDeadEnds => left_dead_ends,
     SkiJumps => ski_jumps,
     PFree => p_free,
@@ -1,4 +1,5 @@
 crate::clap_utils::mk_subcommand! {
     Sum => sum,
     Evaluate => evaluate,
+    Tree => tree,
 }
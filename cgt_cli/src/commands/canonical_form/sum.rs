@@ -1,12 +1,23 @@
 use anyhow::{Context, Result};
-use cgt::short::partizan::canonical_form::CanonicalForm;
+use cgt::short::{
+    impartial::{
+        games::{octal::Octal, quicksort::Quicksort, subtraction::Sub},
+        heap_game::HeapPosition,
+    },
+    partizan::{
+        canonical_form::CanonicalForm, combinators::Impartial, partizan_game::PartizanGame,
+        transposition_table::NoTranspositionTable,
+    },
+};
 use clap::{Parser, arg};
 use std::{fmt::Write, str::FromStr};
 
 /// Sum multiple canonical forms
 #[derive(Parser, Debug, Clone)]
 pub struct Args {
-    /// Games to sum and compute the canonical form
+    /// Games to sum and compute the canonical form. Besides canonical forms, impartial positions
+    /// are accepted as `quicksort:2,3,1`, `sub:1,2,3:7` (subtraction set and heaps) and
+    /// `octal:0.77:5,6` (octal code and heaps)
     #[arg(required = true)]
     games: Vec<String>,
 }
@@ -21,8 +32,7 @@ pub fn run(args: Args) -> Result<()> {
             buf.write_str(" + ")?;
         }
 
-        let canonical_form = CanonicalForm::from_str(input)
-            .ok()
+        let canonical_form = parse_game(input)
             .context(format!("Could not parse game: '{}'", &input))?;
         buf.write_str(&canonical_form.to_string())?;
         result += canonical_form;
@@ -35,3 +45,35 @@ pub fn run(args: Args) -> Result<()> {
 
     Ok(())
 }
+
+fn parse_numbers(input: &str) -> Option<Vec<u32>> {
+    input
+        .split(',')
+        .map(|number| number.trim().parse().ok())
+        .collect()
+}
+
+fn parse_game(input: &str) -> Option<CanonicalForm> {
+    let Some((game, position)) = input.split_once(':') else {
+        return CanonicalForm::from_str(input).ok();
+    };
+    match game {
+        "quicksort" => {
+            let position = Impartial::new(Quicksort::new(parse_numbers(position)?));
+            Some(position.canonical_form(&NoTranspositionTable::new()))
+        }
+        "sub" => {
+            let (subtraction_set, heaps) = position.split_once(':')?;
+            let ruleset = Sub::new(parse_numbers(subtraction_set)?);
+            let position = Impartial::new(HeapPosition::new(ruleset, parse_numbers(heaps)?));
+            Some(position.canonical_form(&NoTranspositionTable::new()))
+        }
+        "octal" => {
+            let (code, heaps) = position.split_once(':')?;
+            let ruleset = Octal::from_str(code).ok()?;
+            let position = Impartial::new(HeapPosition::new(ruleset, parse_numbers(heaps)?));
+            Some(position.canonical_form(&NoTranspositionTable::new()))
+        }
+        _ => None,
+    }
+}
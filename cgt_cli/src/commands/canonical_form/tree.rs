@@ -0,0 +1,55 @@
+use crate::io::FilePathOr;
+use anyhow::{Context, Result};
+use cgt::short::partizan::{canonical_form::CanonicalForm, game_tree::GameTree};
+use clap::{Parser, ValueEnum};
+use std::{
+    io::{BufWriter, Stdout, Write},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    Dot,
+    Json,
+}
+
+/// Export game tree of a canonical form
+#[derive(Parser, Debug)]
+pub struct Args {
+    /// Position to export
+    #[arg(long)]
+    position: String,
+
+    /// Maximum depth of the tree
+    #[arg(long, default_value_t = 3)]
+    depth: usize,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = Format::Dot)]
+    format: Format,
+
+    /// Output path
+    #[arg(long, default_value = "-")]
+    output: FilePathOr<Stdout>,
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn run(args: Args) -> Result<()> {
+    let canonical_form = CanonicalForm::from_str(&args.position)
+        .ok()
+        .context(format!("Could not parse game: '{}'", &args.position))?;
+    let tree = GameTree::from_canonical_form(&canonical_form, args.depth);
+
+    let mut w = BufWriter::new(
+        args.output
+            .create()
+            .context(format!("Could not create file '{}'", args.output))?,
+    );
+    let rendered = match args.format {
+        Format::Dot => tree.to_graphviz(),
+        Format::Json => tree.to_json(),
+    };
+    writeln!(w, "{}", rendered).context(format!("Could not write to file '{}'", args.output))?;
+
+    Ok(())
+}
@@ -0,0 +1,6 @@
+pub use crate::evaluate::Args;
+use cgt::short::partizan::games::cherries::Cherries;
+
+pub fn run(args: Args) -> anyhow::Result<()> {
+    crate::evaluate::run::<Cherries>(args)
+}
@@ -0,0 +1,3 @@
+crate::clap_utils::mk_subcommand! {
+    Evaluate => evaluate,
+}
@@ -0,0 +1,6 @@
+pub use crate::evaluate::Args;
+use cgt::short::partizan::games::clobber::Clobber;
+
+pub fn run(args: Args) -> anyhow::Result<()> {
+    crate::evaluate::run::<Clobber>(args)
+}
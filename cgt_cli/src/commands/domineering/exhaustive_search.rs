@@ -3,9 +3,13 @@ use cgt::{
     grid::{FiniteGrid, small_bit_grid::SmallBitGrid},
     numeric::dyadic_rational_number::DyadicRationalNumber,
     short::partizan::{
-        games::domineering,
+        games::domineering::{self, Domineering},
         partizan_game::PartizanGame,
-        transposition_table::{NoTranspositionTable, ParallelTranspositionTable},
+        canonical_form::CanonicalForm,
+        transposition_table::{
+            BoundedTranspositionTable, NoTranspositionTable, ParallelTranspositionTable,
+            PersistentTranspositionTable,
+        },
     },
 };
 use clap::{Parser, ValueEnum};
@@ -48,13 +52,10 @@ pub struct Args {
     #[arg(long, default_value_t = 5)]
     progress_interval: u64,
 
-    /// Path to read the cache
+    /// Path to a persistent transposition table. Stored positions are reused and new ones are
+    /// appended, so interrupted search can be resumed without recomputing them
     #[arg(long, default_value = None)]
-    cache_read_path: Option<String>,
-
-    /// Path to write the cache
-    #[arg(long, default_value = None)]
-    cache_write_path: Option<String>,
+    transposition_table_path: Option<String>,
 
     /// Do not report positions with this or below this temperature
     #[arg(long, default_value = None)]
@@ -79,6 +80,35 @@ pub struct Args {
     /// Don't use transposition table of game positions and canonical forms
     #[arg(long, default_value_t = false)]
     no_transposition_table: bool,
+
+    /// Maximum number of positions kept in the transposition table. Least recently used positions
+    /// are evicted when the table is full. Unbounded if not set
+    #[arg(long, default_value = None, conflicts_with = "transposition_table_path")]
+    transposition_table_capacity: Option<usize>,
+}
+
+enum Transpositions {
+    None,
+    Unbounded(ParallelTranspositionTable<Domineering>),
+    Bounded(BoundedTranspositionTable<Domineering>),
+    Persistent(PersistentTranspositionTable<Domineering>),
+}
+
+impl Transpositions {
+    fn canonical_form(&self, position: &Domineering) -> CanonicalForm {
+        match self {
+            Transpositions::None => position.canonical_form(&NoTranspositionTable::new()),
+            Transpositions::Unbounded(transposition_table) => {
+                position.canonical_form(transposition_table)
+            }
+            Transpositions::Bounded(transposition_table) => {
+                position.canonical_form(transposition_table)
+            }
+            Transpositions::Persistent(transposition_table) => {
+                position.canonical_form(transposition_table)
+            }
+        }
+    }
 }
 
 struct ProgressTracker {
@@ -129,10 +159,20 @@ pub fn run(args: Args) -> Result<()> {
         );
     }
 
-    let transposition_table = if args.no_transposition_table {
-        None
+    let transpositions = if args.no_transposition_table {
+        Transpositions::None
+    } else if let Some(path) = &args.transposition_table_path {
+        Transpositions::Persistent(
+            PersistentTranspositionTable::open(path)
+                .with_context(|| "Could not open transposition table")?,
+        )
+    } else if let Some(capacity) = args.transposition_table_capacity {
+        if capacity == 0 {
+            bail!("transposition-table-capacity must be positive.");
+        }
+        Transpositions::Bounded(BoundedTranspositionTable::new(capacity))
     } else {
-        Some(ParallelTranspositionTable::new())
+        Transpositions::Unbounded(ParallelTranspositionTable::new())
     };
 
     let output_file =
@@ -186,10 +226,7 @@ pub fn run(args: Args) -> Result<()> {
 
             let thermograph = match progress_tracker.args.thermograph_method {
                 ThermographMethod::CanonicalForm => {
-                    let canonical_form = transposition_table.as_ref().map_or_else(
-                        || grid.canonical_form(&NoTranspositionTable::new()),
-                        |transposition_table| grid.canonical_form(transposition_table),
-                    );
+                    let canonical_form = transpositions.canonical_form(&grid);
                     canonical_form.thermograph()
                 }
                 ThermographMethod::Direct => grid.thermograph_direct(),
@@ -222,6 +259,18 @@ pub fn run(args: Args) -> Result<()> {
         pid.join().unwrap();
     }
 
+    match transpositions {
+        Transpositions::Persistent(transposition_table) => {
+            transposition_table
+                .flush()
+                .with_context(|| "Could not write transposition table")?;
+        }
+        Transpositions::Bounded(transposition_table) => {
+            eprintln!("Transposition table: {}", transposition_table.stats());
+        }
+        Transpositions::None | Transpositions::Unbounded(_) => {}
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,66 @@
+use anyhow::Result;
+use cgt::short::partizan::{
+    canonical_form::CanonicalForm,
+    games::partizan_quicksort::PartizanQuicksort, partizan_game::PartizanGame,
+    transposition_table::ParallelTranspositionTable,
+};
+use clap::{self, Parser, ValueEnum};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum GameValueFilter {
+    None,
+    Number,
+    Zero,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Report {
+    position: String,
+    canonical_form: String,
+}
+
+/// Evaluate all positions of partizan Quicksort game in a given range.
+#[derive(Debug, Clone, Parser)]
+pub struct Args {
+    #[arg(long, default_value_t = 1)]
+    start_range: u32,
+
+    #[arg(long, default_value_t = 6)]
+    end_range: u32,
+
+    #[arg(long, value_enum, default_value_t = GameValueFilter::None)]
+    filter: GameValueFilter,
+}
+
+#[allow(clippy::needless_pass_by_value, clippy::unnecessary_wraps)]
+pub fn run(args: Args) -> Result<()> {
+    let tt = ParallelTranspositionTable::new();
+
+    for max_value in args.start_range..=args.end_range {
+        let sorted_range = (1..=max_value).collect::<Vec<u32>>();
+
+        let range_len = sorted_range.len();
+        for game in sorted_range.into_iter().permutations(range_len) {
+            let game = PartizanQuicksort::new(game);
+            let canonical_form = game.canonical_form(&tt);
+
+            let matches = match args.filter {
+                GameValueFilter::None => true,
+                GameValueFilter::Number => canonical_form.is_number(),
+                GameValueFilter::Zero => canonical_form == CanonicalForm::new_integer(0),
+            };
+
+            if matches {
+                let report = Report {
+                    position: game.to_string(),
+                    canonical_form: canonical_form.to_string(),
+                };
+                println!("{}", serde_json::ser::to_string(&report).unwrap());
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use anyhow::Result;
+use cgt::short::partizan::games::partizan_subtraction::{PartizanSub, Periodicity};
+use clap::{self, Parser};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Report {
+    position: String,
+    canonical_form: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PeriodicityReport {
+    game: String,
+    periodicity: String,
+    proven: bool,
+}
+
+/// Evaluate partizan subtraction game on all heaps in a given range.
+#[derive(Debug, Clone, Parser)]
+pub struct Args {
+    /// Comma separated list of values in the subtraction set of Left
+    #[arg(long, value_delimiter = ',')]
+    left: Vec<u32>,
+
+    /// Comma separated list of values in the subtraction set of Right
+    #[arg(long, value_delimiter = ',')]
+    right: Vec<u32>,
+
+    /// Starting heap size
+    #[arg(long, default_value_t = 0)]
+    start_range: u32,
+
+    /// Final heap size
+    #[arg(long, default_value_t = 20)]
+    end_range: u32,
+
+    /// Report the shortest period that holds in the evaluated range
+    #[arg(long, default_value_t = false)]
+    period: bool,
+}
+
+#[allow(clippy::needless_pass_by_value, clippy::unnecessary_wraps)]
+pub fn run(args: Args) -> Result<()> {
+    let game = PartizanSub::new(args.left.clone(), args.right.clone());
+
+    for (heap, canonical_form) in game
+        .clone()
+        .canonical_forms()
+        .enumerate()
+        .take(args.end_range as usize + 1)
+        .skip(args.start_range as usize)
+    {
+        let report = Report {
+            position: game.clone().position(heap as u32).to_string(),
+            canonical_form: canonical_form.to_string(),
+        };
+        println!("{}", serde_json::ser::to_string(&report).unwrap());
+    }
+
+    if args.period {
+        let periodicity = game.detect_period(args.end_range as usize);
+        let report = PeriodicityReport {
+            game: game.to_string(),
+            periodicity: periodicity
+                .as_ref()
+                .map_or_else(|| String::from("none"), ToString::to_string),
+            proven: periodicity.as_ref().is_some_and(Periodicity::is_proven),
+        };
+        println!("{}", serde_json::ser::to_string(&report).unwrap());
+    }
+
+    Ok(())
+}
@@ -1,9 +1,12 @@
 use anyhow::Result;
 use cgt::{
     numeric::nimber::Nimber,
-    short::impartial::{
-        games::{pseudo_quicksort::PseudoQuicksort, quicksort::Quicksort},
-        impartial_game::ImpartialGame,
+    short::{
+        impartial::{
+            games::{pseudo_quicksort::PseudoQuicksort, quicksort::Quicksort},
+            impartial_game::ImpartialGame,
+        },
+        partizan::transposition_table::ParallelTranspositionTable,
     },
 };
 use clap::{self, Parser, ValueEnum};
@@ -47,8 +50,8 @@ pub struct Args {
 
 // There's no reasonable trait so here we go with a macro
 macro_rules! handle_variant {
-    ($variant:expr, $filter:expr) => {{
-        let game_value = $variant.nim_value();
+    ($variant:expr, $transposition_table:expr, $filter:expr) => {{
+        let game_value = $variant.nim_value($transposition_table);
 
         if $filter(game_value) {
             let report = Report {
@@ -62,6 +65,10 @@ macro_rules! handle_variant {
 
 #[allow(clippy::needless_pass_by_value, clippy::unnecessary_wraps)]
 pub fn run(args: Args) -> Result<()> {
+    // Shorter permutations appear as blocks of longer ones, so values are shared between ranges
+    let quicksort_tt = ParallelTranspositionTable::new();
+    let pseudo_quicksort_tt = ParallelTranspositionTable::new();
+
     for max_value in args.start_range..=args.end_range {
         let sorted_range = (1..=max_value).collect::<Vec<u32>>();
 
@@ -80,8 +87,16 @@ pub fn run(args: Args) -> Result<()> {
         let range_len = sorted_range.len();
         for game in sorted_range.into_iter().permutations(range_len) {
             match args.variant {
-                Variant::Standard => handle_variant!(Quicksort::new(game.clone()), filter),
-                Variant::Pseudo => handle_variant!(PseudoQuicksort::new(game.clone()), filter),
+                Variant::Standard => {
+                    handle_variant!(Quicksort::new(game.clone()), &quicksort_tt, filter);
+                }
+                Variant::Pseudo => {
+                    handle_variant!(
+                        PseudoQuicksort::new(game.clone()),
+                        &pseudo_quicksort_tt,
+                        filter
+                    );
+                }
             }
         }
     }
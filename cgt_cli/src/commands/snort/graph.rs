@@ -2,7 +2,14 @@ use crate::commands::snort::common::{analyze_position, Edge};
 use anyhow::Result;
 use cgt::{
     graph::{adjacency_matrix::undirected::UndirectedGraph, Graph, VertexIndex},
-    short::partizan::games::snort::{Snort, VertexColor, VertexKind},
+    short::partizan::{
+        games::{
+            col::Col,
+            snort::{Snort, VertexColor, VertexKind},
+        },
+        partizan_game::PartizanGame,
+        transposition_table::ParallelTranspositionTable,
+    },
 };
 use clap::Parser;
 
@@ -26,6 +33,10 @@ pub struct Args {
     #[arg(long)]
     /// Do not generate a graphviz graph of the position and immediate children.
     no_graphviz: bool,
+
+    #[arg(long)]
+    /// Also evaluate Col on the same graph, for comparison with Snort.
+    col: bool,
 }
 
 pub fn run(args: Args) -> Result<()> {
@@ -59,6 +70,13 @@ pub fn run(args: Args) -> Result<()> {
     }
     let graph = UndirectedGraph::from_edges(&edges, &vertices);
 
+    if args.col {
+        let col: Col<VertexKind, UndirectedGraph<VertexKind>> = Col::new(graph.clone());
+        let canonical_form = col.canonical_form(&ParallelTranspositionTable::new());
+        eprintln!("Col Canonical Form: {}", canonical_form);
+        eprintln!("Col Temperature: {}", canonical_form.temperature());
+    }
+
     let position = Snort::new(graph);
     analyze_position(position, !args.no_graphviz)?;
 
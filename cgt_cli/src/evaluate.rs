@@ -1,14 +1,14 @@
 use crate::io::FilePathOr;
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use cgt::{
     drawing::{Draw, svg, tiny_skia},
     short::partizan::{
-        partizan_game::PartizanGame, transposition_table::ParallelTranspositionTable,
+        partizan_game::PartizanGame,
+        transposition_table::{BoundedTranspositionTable, ParallelTranspositionTable},
     },
 };
 use clap::Parser;
 use std::{
-    fmt::Debug,
     io::{BufWriter, Stdout, Write},
     str::FromStr,
 };
@@ -27,15 +27,20 @@ pub struct Args {
     /// PNG render output path
     #[arg(long, default_value = None)]
     output_png: Option<FilePathOr<Stdout>>,
+
+    /// Maximum number of positions kept in the transposition table. Unbounded if not set
+    #[arg(long, default_value = None)]
+    transposition_table_capacity: Option<usize>,
 }
 
 #[allow(clippy::needless_pass_by_value)]
 pub fn run<Game>(args: Args) -> Result<()>
 where
     Game: FromStr + Draw + PartizanGame,
-    <Game as FromStr>::Err: Debug,
 {
-    let position: Game = Game::from_str(&args.position).expect("Could not parse position");
+    let position: Game = Game::from_str(&args.position)
+        .ok()
+        .context("Could not parse the position")?;
 
     if let Some(svg_fp) = &args.output_svg {
         let mut w = BufWriter::new(
@@ -66,8 +71,16 @@ where
             .context(format!("Could not write to file '{}'", png_fp))?;
     }
 
-    let tt = ParallelTranspositionTable::new();
-    let canonical_form = position.canonical_form(&tt);
+    let canonical_form = match args.transposition_table_capacity {
+        Some(0) => bail!("transposition-table-capacity must be positive."),
+        Some(capacity) => {
+            let tt = BoundedTranspositionTable::new(capacity);
+            let canonical_form = position.canonical_form(&tt);
+            eprintln!("Transposition table: {}", tt.stats());
+            canonical_form
+        }
+        None => position.canonical_form(&ParallelTranspositionTable::new()),
+    };
     println!("Canonical Form: {}", canonical_form);
     println!("Temperature: {}", canonical_form.temperature());
 
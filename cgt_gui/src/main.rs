@@ -1,9 +1,16 @@
 use crate::widgets::{
-    amazons::AmazonsWindow, canonical_form::CanonicalFormWindow,
-    digraph_placement::DigraphPlacementWindow, domineering::DomineeringWindow,
-    fission::FissionWindow, graph_editor::GraphWindow, konane::KonaneWindow,
-    quelhas::QuelhasWindow, resolving_set::ResolvingSetWindow, ski_jumps::SkiJumpsWindow,
-    snort::SnortWindow, toads_and_frogs::ToadsAndFrogsWindow,
+    amazons::AmazonsWindow,
+    canonical_form::CanonicalFormWindow,
+    digraph_placement::DigraphPlacementWindow,
+    domineering::DomineeringWindow,
+    fission::FissionWindow,
+    graph_editor::GraphWindow,
+    konane::KonaneWindow,
+    node_coloring::{ColWindow, SnortWindow},
+    quelhas::QuelhasWindow,
+    resolving_set::ResolvingSetWindow,
+    ski_jumps::SkiJumpsWindow,
+    toads_and_frogs::ToadsAndFrogsWindow,
 };
 use cgt::{
     graph::adjacency_matrix::{directed::DirectedGraph, undirected::UndirectedGraph},
@@ -12,6 +19,7 @@ use cgt::{
         canonical_form::CanonicalForm,
         games::{
             amazons::Amazons,
+            col::Col,
             digraph_placement::{self, DigraphPlacement},
             domineering::Domineering,
             fission::Fission,
@@ -224,6 +232,7 @@ pub enum Task {
     EvalSkiJumps(EvalTask<SkiJumps>),
     EvalToadsAndFrogs(EvalTask<ToadsAndFrogs>),
     EvalSnort(EvalTask<Snort<snort::VertexKind, UndirectedGraph<snort::VertexKind>>>),
+    EvalCol(EvalTask<Col<snort::VertexKind, UndirectedGraph<snort::VertexKind>>>),
     EvalDigraphPlacement(
         EvalTask<
             DigraphPlacement<
@@ -285,6 +294,10 @@ pub enum UpdateKind {
         Snort<snort::VertexKind, UndirectedGraph<snort::VertexKind>>,
         Details,
     ),
+    ColDetails(
+        Col<snort::VertexKind, UndirectedGraph<snort::VertexKind>>,
+        Details,
+    ),
     DigraphPlacementDetails(
         DigraphPlacement<
             digraph_placement::VertexColor,
@@ -312,6 +325,7 @@ pub struct SchedulerContext {
     toads_and_frogs_tt: ParallelTranspositionTable<ToadsAndFrogs>,
     snort_tt:
         ParallelTranspositionTable<Snort<snort::VertexKind, UndirectedGraph<snort::VertexKind>>>,
+    col_tt: ParallelTranspositionTable<Col<snort::VertexKind, UndirectedGraph<snort::VertexKind>>>,
     digraph_placement_tt: ParallelTranspositionTable<
         DigraphPlacement<
             digraph_placement::VertexColor,
@@ -379,6 +393,9 @@ fn scheduler(ctx: SchedulerContext) {
                 Task::EvalSnort(task) => {
                     handle_game_update!(task, SnortDetails, snort_tt);
                 }
+                Task::EvalCol(task) => {
+                    handle_game_update!(task, ColDetails, col_tt);
+                }
                 Task::EvalDigraphPlacement(task) => {
                     handle_game_update!(task, DigraphPlacementDetails, digraph_placement_tt);
                 }
@@ -407,6 +424,7 @@ fn main() {
         ski_jumps_tt: ParallelTranspositionTable::new(),
         toads_and_frogs_tt: ParallelTranspositionTable::new(),
         snort_tt: ParallelTranspositionTable::new(),
+        col_tt: ParallelTranspositionTable::new(),
         digraph_placement_tt: ParallelTranspositionTable::new(),
     };
 
@@ -528,6 +546,9 @@ fn main() {
                 if ui.menu_item("Snort") {
                     new_window!(SnortWindow);
                 }
+                if ui.menu_item("Col") {
+                    new_window!(ColWindow);
+                }
                 if ui.menu_item("Digraph Placement") {
                     new_window!(DigraphPlacementWindow);
                 }
@@ -16,10 +16,10 @@ pub mod domineering;
 pub mod fission;
 pub mod graph_editor;
 pub mod konane;
+pub mod node_coloring;
 pub mod quelhas;
 pub mod resolving_set;
 pub mod ski_jumps;
-pub mod snort;
 pub mod toads_and_frogs;
 
 pub const TILE_SIZE: f32 = 64.0;
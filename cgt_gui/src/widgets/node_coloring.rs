@@ -16,9 +16,57 @@ use cgt::{
     has::Has,
     impl_has,
     numeric::v2f::V2f,
-    short::partizan::games::snort::{Snort, VertexColor, VertexKind},
+    short::partizan::games::{
+        col::ColRule,
+        node_coloring::{ColoringRule, NodeColoring, VertexColor, VertexKind},
+        snort::SnortRule,
+    },
 };
-use std::fmt::Write;
+use std::fmt::{Debug, Write};
+
+pub type Position<R> = NodeColoring<R, VertexKind, UndirectedGraph<VertexKind>>;
+
+/// Node coloring ruleset that can be evaluated in the background
+pub trait GuiColoringRule: ColoringRule + Debug + 'static {
+    const TITLE: &'static str;
+    const FILE_PREFIX: &'static str;
+
+    fn eval_task(task: EvalTask<Position<Self>>) -> Task;
+
+    fn details(update: UpdateKind) -> Option<(Position<Self>, Details)>;
+}
+
+impl GuiColoringRule for SnortRule {
+    const TITLE: &'static str = "Snort";
+    const FILE_PREFIX: &'static str = "snort";
+
+    fn eval_task(task: EvalTask<Position<Self>>) -> Task {
+        Task::EvalSnort(task)
+    }
+
+    fn details(update: UpdateKind) -> Option<(Position<Self>, Details)> {
+        match update {
+            UpdateKind::SnortDetails(game, details) => Some((game, details)),
+            _ => None,
+        }
+    }
+}
+
+impl GuiColoringRule for ColRule {
+    const TITLE: &'static str = "Col";
+    const FILE_PREFIX: &'static str = "col";
+
+    fn eval_task(task: EvalTask<Position<Self>>) -> Task {
+        Task::EvalCol(task)
+    }
+
+    fn details(update: UpdateKind) -> Option<(Position<Self>, Details)> {
+        match update {
+            UpdateKind::ColDetails(game, details) => Some((game, details)),
+            _ => None,
+        }
+    }
+}
 
 imgui_enum! {
     #[derive(Debug, Clone, Copy)]
@@ -45,8 +93,11 @@ impl_has!(PositionedVertex -> kind -> VertexKind);
 impl_has!(PositionedVertex -> position -> V2f);
 
 #[derive(Debug, Clone)]
-pub struct SnortWindow {
-    game: AccessTracker<Snort<PositionedVertex, UndirectedGraph<PositionedVertex>>>,
+pub struct NodeColoringWindow<R>
+where
+    R: GuiColoringRule,
+{
+    game: AccessTracker<NodeColoring<R, PositionedVertex, UndirectedGraph<PositionedVertex>>>,
     reposition_mode: RepositionMode,
     editing_mode: GraphEditingMode,
     alternating_moves: bool,
@@ -54,11 +105,17 @@ pub struct SnortWindow {
     details: Option<Details>,
 }
 
-impl SnortWindow {
-    pub fn new() -> SnortWindow {
-        SnortWindow {
+pub type SnortWindow = NodeColoringWindow<SnortRule>;
+pub type ColWindow = NodeColoringWindow<ColRule>;
+
+impl<R> NodeColoringWindow<R>
+where
+    R: GuiColoringRule,
+{
+    pub fn new() -> NodeColoringWindow<R> {
+        NodeColoringWindow {
             // caterpillar C(4, 3, 4)
-            game: AccessTracker::new(Snort::new(UndirectedGraph::from_edges(
+            game: AccessTracker::new(NodeColoring::new(UndirectedGraph::from_edges(
                 &[
                     // left
                     (VertexIndex { index: 0 }, VertexIndex { index: 4 }),
@@ -140,24 +197,27 @@ impl SnortWindow {
     }
 }
 
-impl IsCgtWindow for TitledWindow<SnortWindow> {
-    impl_titled_window!("Snort");
+impl<R> IsCgtWindow for TitledWindow<NodeColoringWindow<R>>
+where
+    R: GuiColoringRule,
+{
+    impl_titled_window!(R::TITLE);
 
     fn initialize(&mut self, ctx: &GuiContext) {
         self.content.reposition_circle();
         self.content.reposition(V2f { x: 350.0, y: 400.0 });
         let graph = self.content.game.graph.map(|v| v.kind);
         ctx.schedule_task(
-            "Snort",
-            crate::Task::EvalSnort(crate::EvalTask {
+            R::TITLE,
+            R::eval_task(crate::EvalTask {
                 window: self.window_id,
-                game: Snort::new(graph),
+                game: NodeColoring::new(graph),
             }),
         );
     }
     fn update(&mut self, update: crate::UpdateKind) {
         let graph = self.content.game.graph.map(|v| v.kind);
-        if let UpdateKind::SnortDetails(game, details) = update {
+        if let Some((game, details)) = R::details(update) {
             if graph == game.graph {
                 self.content.details = Some(details);
             }
@@ -193,7 +253,7 @@ impl IsCgtWindow for TitledWindow<SnortWindow> {
                     }
                     save_button(
                         ui,
-                        "snort",
+                        R::FILE_PREFIX,
                         &*self.content.game,
                         self.content.details.as_ref().map(|d| &d.thermograph),
                     );
@@ -207,7 +267,7 @@ impl IsCgtWindow for TitledWindow<SnortWindow> {
                 should_reposition = ui.button("Reposition");
                 ui.same_line();
                 if ui.button("Clear") {
-                    *self.content.game = Snort::new(UndirectedGraph::empty(&[]));
+                    *self.content.game = NodeColoring::new(UndirectedGraph::empty(&[]));
                 }
 
                 self.content.editing_mode.combo(ui, "Edit Mode");
@@ -372,10 +432,10 @@ impl IsCgtWindow for TitledWindow<SnortWindow> {
                     self.content.details = None;
                     let graph = self.content.game.graph.map(|v| v.kind);
                     ctx.schedule_task(
-                        "Snort",
-                        Task::EvalSnort(EvalTask {
+                        R::TITLE,
+                        R::eval_task(EvalTask {
                             window: self.window_id,
-                            game: Snort::new(graph),
+                            game: NodeColoring::new(graph),
                         }),
                     );
                 }
@@ -98,7 +98,9 @@ impl IsCgtWindow for TitledWindow<ToadsAndFrogsWindow> {
                 let mut canvas =
                     imgui::Canvas::new(ui, &draw_list, ctx.large_font_id, &mut self.scratch_buffer);
                 self.content.game.draw(&mut canvas);
-                if let Some((x, _)) = canvas.clicked_tile(&self.content.game.grid()) {
+                if let Some(grid) = self.content.game.grid()
+                    && let Some((x, _)) = canvas.clicked_tile(&grid)
+                {
                     let grid_x = x as usize;
 
                     let mut place_tile = |tile| {